@@ -18,20 +18,57 @@ impl Metadata for ContractMetadata {
 #[derive(Default, Encode, Decode, TypeInfo)]
 pub struct NFTPixelboardState {
     pub owner: ActorId,
+    /// An address proposed by the owner as a new owner that hasn't accepted
+    /// the ownership yet.
+    pub pending_owner: Option<ActorId>,
     pub block_side_length: BlockSideLength,
     pub pixel_price: u128,
     pub resolution: Resolution,
     pub commission_percentage: u8,
+    pub royalty_percentage: u8,
     pub painting: Vec<Color>,
+    /// A copy of the initial painting that can be restored in place of burned
+    /// NFTs.
+    pub background: Vec<Color>,
 
     pub rectangles_by_token_ids: Vec<(TokenId, Rectangle)>,
     pub tokens_by_rectangles: Vec<(Rectangle, TokenInfo)>,
+    /// A total area of minted NFTs & rectangles reserved by pending mints.
+    pub minted_area: u32,
 
     pub ft_program: ActorId,
     pub nft_program: ActorId,
+    pub payment_mode: PaymentMode,
+    /// FT programs accepted in addition to the default currency & prices of a
+    /// free pixel in them.
+    pub currencies: Vec<(ActorId, u128)>,
 
     pub txs: Vec<(ActorId, (TransactionId, NFTPixelboardAction))>,
     pub tx_id: TransactionId,
+    /// Fungible tokens held by a pixelboard in pending transactions.
+    pub escrows: Vec<(ActorId, u128)>,
+    /// Block heights at which transactions that are being processed or were
+    /// interrupted were started.
+    pub txs_in_progress: Vec<(ActorId, BlockHeight)>,
+    /// Funds credited to accounts by currencies that can be withdrawn by
+    /// [`NFTPixelboardAction::Withdraw`].
+    pub balances: Vec<(ActorId, Vec<(Currency, u128)>)>,
+    pub royalties: Vec<(TokenId, Royalty)>,
+    /// Recipients of minting fees & resale commissions with their percentage
+    /// shares. If it's empty, the owner is the only recipient.
+    pub beneficiaries: Vec<(ActorId, u8)>,
+    pub pricing_zones: Vec<PricingZone>,
+    pub pricing_curve: Option<PricingCurve>,
+
+    pub auctions: Vec<(TokenId, Auction)>,
+    pub dutch_auctions: Vec<(TokenId, DutchAuction)>,
+    pub offers: Vec<(TokenId, Vec<(ActorId, u128)>)>,
+
+    /// Kinds of actions that are paused.
+    pub paused: Vec<ActionKind>,
+
+    /// A block height at which this state was read.
+    pub block_height: BlockHeight,
 }
 /// The maximum price that can be set to a pixel.
 ///
@@ -46,6 +83,13 @@ pub struct NFTPixelboardState {
 /// avoid an overflow, the number must be divided by 100. Hence 2⁹⁶ / 100.
 pub const MAX_PIXEL_PRICE: u128 = 2u128.pow(96) / 100;
 
+/// A number of blocks after which a transaction that's still being processed
+/// is considered interrupted.
+///
+/// Until then, the transaction may be waiting for a reply from an FT or NFT
+/// program, so it can be neither continued nor cancelled.
+pub const TX_TIMEOUT: BlockHeight = 100;
+
 /// A block side length.
 ///
 /// It's also used to store pixel [`Coordinates`], [`Resolution`] of a canvas,
@@ -55,6 +99,11 @@ pub type BlockSideLength = u16;
 pub type Color = u8;
 /// A transaction id for tracking transactions in the fungible token contract.
 pub type TransactionId = u64;
+/// A block height of the network.
+pub type BlockHeight = u32;
+/// An FT program address used for payments. [`None`] stands for the default
+/// currency of a pixelboard.
+pub type Currency = Option<ActorId>;
 
 /// Coordinates of the corners of an NFT rectangle on a canvas.
 #[derive(Decode, Encode, TypeInfo, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Default)]
@@ -71,6 +120,121 @@ impl Rectangle {
     pub fn height(&self) -> BlockSideLength {
         self.bottom_right_corner.y - self.top_left_corner.y
     }
+
+    /// Returns a pixel count in this rectangle.
+    pub fn area(&self) -> u32 {
+        self.width() as u32 * self.height() as u32
+    }
+
+    /// Checks whether a pixel with `coordinates` lies inside this rectangle.
+    pub fn contains(&self, coordinates: Coordinates) -> bool {
+        (self.top_left_corner.x..self.bottom_right_corner.x).contains(&coordinates.x)
+            && (self.top_left_corner.y..self.bottom_right_corner.y).contains(&coordinates.y)
+    }
+
+    /// Returns a common part of this & `other` rectangles or [`None`] if they
+    /// don't overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self {
+            top_left_corner: Coordinates {
+                x: self.top_left_corner.x.max(other.top_left_corner.x),
+                y: self.top_left_corner.y.max(other.top_left_corner.y),
+            },
+            bottom_right_corner: Coordinates {
+                x: self.bottom_right_corner.x.min(other.bottom_right_corner.x),
+                y: self.bottom_right_corner.y.min(other.bottom_right_corner.y),
+            },
+        };
+
+        (intersection.top_left_corner.x < intersection.bottom_right_corner.x
+            && intersection.top_left_corner.y < intersection.bottom_right_corner.y)
+            .then_some(intersection)
+    }
+}
+
+/// A part of a canvas with its own price of a free pixel.
+#[derive(Decode, Encode, TypeInfo, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct PricingZone {
+    pub rectangle: Rectangle,
+    pub pixel_price: u128,
+}
+
+/// A way of raising a price of a free pixel as a canvas fills up.
+///
+/// A curve depends on a percentage of a canvas area that's already minted,
+/// and a raised price never exceeds [`MAX_PIXEL_PRICE`].
+#[derive(Decode, Encode, TypeInfo, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PricingCurve {
+    /// Each minted percent of a canvas adds `slope` percents of an initial
+    /// price.
+    Linear { slope: u16 },
+    /// Each minted percent of a canvas raises a price by `rate` percents of
+    /// its previous value.
+    Exponential { rate: u16 },
+}
+
+impl PricingCurve {
+    /// Returns `pixel_price` raised according to `minted_percentage` of a
+    /// canvas area.
+    pub fn pixel_price(&self, pixel_price: u128, minted_percentage: u8) -> u128 {
+        match *self {
+            Self::Linear { slope } => pixel_price
+                .checked_mul(100 + slope as u128 * minted_percentage as u128)
+                .map_or(MAX_PIXEL_PRICE, |price| (price / 100).min(MAX_PIXEL_PRICE)),
+            Self::Exponential { rate } => {
+                let mut price = pixel_price;
+
+                for _ in 0..minted_percentage {
+                    if price >= MAX_PIXEL_PRICE {
+                        break;
+                    }
+
+                    price = price
+                        .checked_mul(100 + rate as u128)
+                        .map_or(MAX_PIXEL_PRICE, |price| (price / 100).min(MAX_PIXEL_PRICE));
+                }
+
+                price
+            }
+        }
+    }
+}
+
+/// Returns a percentage of `canvas_area` covered by `minted_area`.
+pub fn minted_percentage(minted_area: u32, canvas_area: u32) -> u8 {
+    (minted_area as u64 * 100 / canvas_area.max(1) as u64).min(100) as u8
+}
+
+/// Calculates a price of minting `rectangle` in the default currency.
+///
+/// Pixels inside each of `zones` cost its `pixel_price`, and the rest cost
+/// `pixel_price`. `zones` mustn't overlap. If `curve` is set, all these prices
+/// are raised by it according to `minted_percentage` of a canvas area.
+pub fn mint_price(
+    rectangle: &Rectangle,
+    pixel_price: u128,
+    zones: &[PricingZone],
+    curve: Option<PricingCurve>,
+    minted_percentage: u8,
+) -> u128 {
+    let raise = |pixel_price| {
+        curve.map_or(pixel_price, |curve| {
+            curve.pixel_price(pixel_price, minted_percentage)
+        })
+    };
+    let mut free_pixel_count = rectangle.area() as u128;
+    let mut price = 0;
+
+    for zone in zones {
+        if let Some(intersection) = rectangle.intersection(&zone.rectangle) {
+            let pixel_count = intersection.area() as u128;
+
+            free_pixel_count -= pixel_count;
+            price += pixel_count * raise(zone.pixel_price);
+        }
+    }
+
+    price + free_pixel_count * raise(pixel_price)
 }
 
 impl
@@ -105,6 +269,94 @@ impl From<(BlockSideLength, BlockSideLength)> for Coordinates {
     }
 }
 
+/// A block-aligned line along which an NFT [`Rectangle`] is split.
+#[derive(Decode, Encode, TypeInfo, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SplitLine {
+    /// A horizontal line with the given `y` coordinate.
+    Horizontal(BlockSideLength),
+    /// A vertical line with the given `x` coordinate.
+    Vertical(BlockSideLength),
+}
+
+/// A change of a pixelboard configuration made by
+/// [`NFTPixelboardAction::UpdateConfig`].
+#[derive(Decode, Encode, TypeInfo, PartialEq, Eq, Clone, Debug)]
+pub enum ConfigUpdate {
+    /// Changes a price of a free pixel.
+    ///
+    /// Mustn't be more than [`MAX_PIXEL_PRICE`].
+    PixelPrice(u128),
+    /// Changes a commission percentage that'll be included in each NFT sale.
+    ///
+    /// Mustn't be more than 100.
+    CommissionPercentage(u8),
+    /// Changes an FT program address used for payments.
+    ///
+    /// Mustn't be [`ActorId::zero()`]. Can't be changed while a pixelboard
+    /// holds fungible tokens of offers, auction bids, pending transactions or
+    /// balances.
+    FTProgram(ActorId),
+    /// Accepts an FT program as an additional currency with a given price of
+    /// a free pixel or updates the price if it's already accepted.
+    ///
+    /// `ft_program` mustn't be [`ActorId::zero()`] & `pixel_price` mustn't be
+    /// more than [`MAX_PIXEL_PRICE`].
+    AddCurrency {
+        ft_program: ActorId,
+        pixel_price: u128,
+    },
+    /// Stops accepting an FT program as a currency of new mints & listings.
+    ///
+    /// NFTs that are already for sale in this currency stay for sale.
+    RemoveCurrency(ActorId),
+    /// Replaces recipients of minting fees & resale commissions with their
+    /// percentage shares.
+    ///
+    /// Shares must sum up to 100 & recipients mustn't be [`ActorId::zero()`].
+    /// Each recipient is credited with its share rounded down, and the
+    /// rounding remainder is credited to the first one. An empty list makes
+    /// the owner of a pixelboard the only recipient.
+    Beneficiaries(Vec<(ActorId, u8)>),
+    /// Replaces [`PricingZone`]s of a canvas.
+    ///
+    /// Zones mustn't overlap or be out of a canvas, and their pixel prices
+    /// mustn't be more than [`MAX_PIXEL_PRICE`]. Zones are priced in the
+    /// default currency, so a rectangle that intersects any of them can be
+    /// minted only in it.
+    PricingZones(Vec<PricingZone>),
+}
+
+/// A kind of [`NFTPixelboardAction`]s that can be paused by
+/// [`NFTPixelboardAction::Pause`].
+///
+/// Owner actions & withdrawals are never paused.
+#[derive(Decode, Encode, TypeInfo, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum ActionKind {
+    /// [`NFTPixelboardAction::Mint`] & [`NFTPixelboardAction::MintBatch`].
+    Minting,
+    /// [`NFTPixelboardAction::Buy`], [`NFTPixelboardAction::BuyBatch`],
+    /// [`NFTPixelboardAction::ChangeSaleState`], auctions & offers.
+    Trading,
+    /// [`NFTPixelboardAction::Paint`].
+    Painting,
+    /// [`NFTPixelboardAction::Transfer`], [`NFTPixelboardAction::Merge`],
+    /// [`NFTPixelboardAction::Split`], [`NFTPixelboardAction::Burn`] & owner
+    /// synchronization.
+    Transferring,
+}
+
+/// A transaction that hasn't been completed yet.
+#[derive(Decode, Encode, TypeInfo, PartialEq, Eq, Clone)]
+pub struct PendingTx {
+    pub tx_id: TransactionId,
+    pub action: NFTPixelboardAction,
+    /// An amount of fungible tokens held by a pixelboard in this transaction.
+    pub escrow: u128,
+    /// A block height at which this transaction was started if it's being
+    /// processed or was interrupted.
+    pub started_at: Option<BlockHeight>,
+}
+
 /// A resolution of a canvas.
 #[derive(Decode, Encode, Default, Clone, Copy, TypeInfo, Debug, PartialEq, Eq)]
 pub struct Resolution {
@@ -129,6 +381,10 @@ pub struct TokenInfo {
     pub owner: ActorId,
     /// If this field is [`None`], then this NFT isn't for sale, and vice versa.
     ///
+    /// If this NFT is on a [`DutchAuction`], then this field contains its start
+    /// pixel price, and a current one should be calculated by
+    /// [`DutchAuction::pixel_price()`].
+    ///
     /// To calculate a price of the entire NFT, its area must be calculated and
     /// multiplied by `pixel_price`. The area can be calculated by multiplying a
     /// [width](`Rectangle::width`) & [height](`Rectangle::height`) from NFT
@@ -136,6 +392,86 @@ pub struct TokenInfo {
     /// [`NFTPixelboardStateQuery::TokenInfo`] using `token_id` from this
     /// struct.
     pub pixel_price: Option<u128>,
+    /// A [`Currency`] in which `pixel_price` is set.
+    pub currency: Currency,
+}
+
+/// A royalty of the original minter of an NFT that is paid on each resale.
+///
+/// A royalty is calculated from a price of an NFT in the same way as a resale
+/// commission. If they both exceed the price, then the royalty is reduced to
+/// what's left after the commission.
+#[derive(Decode, Encode, TypeInfo, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Royalty {
+    pub minter: ActorId,
+    pub percentage: u8,
+}
+
+/// An English auction of an NFT minted on a pixelboard.
+///
+/// While an auction lasts, the NFT is held by a pixelboard program, and a
+/// highest bid is held by it in fungible tokens.
+#[derive(Decode, Encode, TypeInfo, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Auction {
+    /// An owner of the NFT that started the auction.
+    pub seller: ActorId,
+    /// A minimum price of each pixel that the NFT occupies that a first bid
+    /// must offer.
+    pub min_pixel_price: u128,
+    /// A block height starting from which the auction can be settled and no
+    /// more bids are accepted.
+    pub ends_at: BlockHeight,
+    /// A bidder with its price of each pixel that the NFT occupies, or [`None`]
+    /// if there're no bids yet.
+    pub highest_bid: Option<(ActorId, u128)>,
+}
+
+/// A Dutch auction of an NFT minted on a pixelboard.
+///
+/// A price of each pixel that the NFT occupies falls linearly from
+/// `start_pixel_price` to `floor_pixel_price` during `duration` blocks, and
+/// then stays equal to `floor_pixel_price`.
+#[derive(Decode, Encode, TypeInfo, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DutchAuction {
+    pub start_pixel_price: u128,
+    pub floor_pixel_price: u128,
+    /// A block height at which the auction started.
+    pub started_at: BlockHeight,
+    /// A number of blocks during which a pixel price falls.
+    pub duration: BlockHeight,
+}
+
+impl DutchAuction {
+    /// Returns a price of each pixel that the NFT occupies at `block_height`.
+    pub fn pixel_price(&self, block_height: BlockHeight) -> u128 {
+        let elapsed = block_height
+            .saturating_sub(self.started_at)
+            .min(self.duration);
+
+        if elapsed == 0 {
+            return self.start_pixel_price;
+        }
+
+        // Can't overflow because the pixel price difference is less than
+        // `MAX_PIXEL_PRICE` & `elapsed` is less than `BlockHeight::MAX`.
+        let price_drop = (self.start_pixel_price - self.floor_pixel_price) * elapsed as u128
+            / self.duration as u128;
+
+        self.start_pixel_price - price_drop
+    }
+}
+
+/// A way of paying for NFTs on a pixelboard.
+#[derive(Decode, Encode, TypeInfo, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum PaymentMode {
+    /// Payments are made with fungible tokens of an FT program.
+    #[default]
+    FungibleToken,
+    /// Payments are made with a value attached to a message
+    /// ([`msg::value()`]). Overpayment is returned with a reply.
+    ///
+    /// [`msg::value()`]: gstd::msg::value
+    Value,
 }
 
 /// Initializes the NFT pixelboard program.
@@ -152,7 +488,9 @@ pub struct TokenInfo {
 /// calculated by multiplying a [width](`Resolution#structfield.width`) &
 /// [height](`Resolution#structfield.height`) from `resolution`).
 /// * `commission_percentage` mustn't be more than 100.
-/// * `ft_program` address mustn't be [`ActorId::zero()`].
+/// * `royalty_percentage` mustn't be more than 100.
+/// * `ft_program` address mustn't be [`ActorId::zero()`] if `payment_mode` is
+/// [`PaymentMode::FungibleToken`].
 /// * `nft_program` address mustn't be [`ActorId::zero()`].
 #[derive(Decode, Encode, TypeInfo, Clone)]
 pub struct InitNFTPixelboard {
@@ -173,6 +511,12 @@ pub struct InitNFTPixelboard {
     pub resolution: Resolution,
     /// A commission percentage that'll be included in each NFT resale.
     pub commission_percentage: u8,
+    /// A default [`Royalty`] percentage of NFT minters that'll be included in
+    /// each NFT resale.
+    pub royalty_percentage: u8,
+    /// A [`PricingCurve`] that raises pixel prices as a canvas fills up. If
+    /// it's [`None`], pixel prices stay flat.
+    pub pricing_curve: Option<PricingCurve>,
     /// A painting that'll be displayed on the free territory of a pixelboard.
     pub painting: Vec<Color>,
 
@@ -180,6 +524,8 @@ pub struct InitNFTPixelboard {
     pub ft_program: ActorId,
     /// An NFT program address.
     pub nft_program: ActorId,
+    /// A way of paying for NFTs.
+    pub payment_mode: PaymentMode,
 }
 
 /// Sends a program info about what it should do.
@@ -187,7 +533,9 @@ pub struct InitNFTPixelboard {
 pub enum NFTPixelboardAction {
     /// Mints one NFT on a pixelboard with given `token_metadata` & `painting`.
     ///
-    /// Transfers a minted NFT to [`msg::source()`].
+    /// Transfers a minted NFT to [`msg::source()`]. A payment is credited to
+    /// balances of beneficiaries of a pixelboard (see
+    /// [`ConfigUpdate::Beneficiaries`]).
     ///
     /// # Requirements
     /// * `rectangle` coordinates mustn't be out of a canvas.
@@ -207,7 +555,17 @@ pub enum NFTPixelboardAction {
     /// pixel. The area can be calculated by multiplying a
     /// [width](`Rectangle::width`) & [height](`Rectangle::height`) from
     /// `rectangle`. The price of a free pixel can be obtained by
-    /// [`NFTPixelboardStateQuery::PixelPrice`].
+    /// [`NFTPixelboardStateQuery::PixelPrice`]. Pixels in [`PricingZone`]s
+    /// cost their own price, and a [`PricingCurve`] raises all prices as a
+    /// canvas fills up, so the exact number of tokens can be calculated by
+    /// [`mint_price()`]. If a pixelboard uses
+    /// [`PaymentMode::Value`], then these tokens must be attached to a message
+    /// as a value.
+    /// * `currency` must be [`None`] or an FT program accepted by
+    /// [`ConfigUpdate::AddCurrency`]. In the latter case, fungible tokens are
+    /// paid in this FT program at its own price of a free pixel & `rectangle`
+    /// mustn't intersect [`PricingZone`]s.
+    /// * `royalty_percentage` mustn't be more than 100.
     ///
     /// On success, returns [`NFTPixelboardEvent::Minted`].
     ///
@@ -218,6 +576,13 @@ pub enum NFTPixelboardAction {
         /// A painting that'll be displayed in a place of an NFT on a pixelboard
         /// after a successful minting.
         painting: Vec<Color>,
+        /// A [`Currency`] to pay with.
+        currency: Currency,
+        /// A [`Royalty`] percentage of [`msg::source()`] on resales of an NFT.
+        /// [`None`] stands for the default one from [`InitNFTPixelboard`].
+        ///
+        /// [`msg::source()`]: gstd::msg::source
+        royalty_percentage: Option<u8>,
     },
 
     /// Buys an NFT minted on a pixelboard.
@@ -225,9 +590,20 @@ pub enum NFTPixelboardAction {
     /// Transfers a purchased NFT from a pixelboard program to
     /// [`msg::source()`].
     ///
-    /// **Note:** If [`msg::source()`] has enough fungible tokens to pay a
-    /// resale commission but not the entire NFT, then the commission will still
-    /// be withdrawn from its account.
+    /// If an NFT is on a [`DutchAuction`], then its pixel price is the one at
+    /// the current block height.
+    ///
+    /// The entire price is held by a pixelboard program until an NFT is
+    /// transferred & only then credited to balances of the seller,
+    /// beneficiaries of a pixelboard (a resale commission) & the original
+    /// minter (a [`Royalty`]). If the transfer fails, the price
+    /// is refunded to [`msg::source()`].
+    ///
+    /// A sale is checked again after the price is held & when a purchase is
+    /// continued by [`NFTPixelboardAction::ContinueTx`]. If meanwhile an NFT
+    /// was sold, taken off sale or got more expensive, the held price is
+    /// refunded & the purchase fails. If it got cheaper, the difference is
+    /// credited to [`msg::source()`]'s balance.
     ///
     /// # Requirements
     /// * An NFT must be minted on a pixelboard.
@@ -237,12 +613,20 @@ pub enum NFTPixelboardAction {
     /// * [`msg::source()`] must have enough fungible tokens to buy all pixels
     /// that an NFT occupies. This can be found out by
     /// [`NFTPixelboardStateQuery::TokenInfo`]. See also the documentation of
-    /// [`TokenInfo#structfield.pixel_price`].
+    /// [`TokenInfo#structfield.pixel_price`]. If a pixelboard uses
+    /// [`PaymentMode::Value`], then these tokens must be attached to a message
+    /// as a value.
+    /// * `currency` must equal a currency in which an NFT is for sale. See
+    /// [`TokenInfo#structfield.currency`].
     ///
     /// On success, returns [`NFTPixelboardEvent::Bought`].
     ///
     /// [`msg::source()`]: gstd::msg::source
-    Buy(TokenId),
+    Buy {
+        token_id: TokenId,
+        /// A [`Currency`] to pay with.
+        currency: Currency,
+    },
 
     /// Changes a sale state of an NFT minted on a pixelboard.
     ///
@@ -257,15 +641,21 @@ pub enum NFTPixelboardAction {
     /// Assigning the `pixel_price` to [`None`] will transfer an NFT back to its
     /// owner & remove an NFT from sale.
     ///
-    /// **Note:** A commission is included in each NFT resale, so a seller
-    /// will receive not all fungible tokens but tokens with a commission
-    /// deduction. A commission percentage can be obtained by
-    /// [`NFTPixelboardStateQuery::CommissionPercentage`].
+    /// If an NFT is on a [`DutchAuction`], then the auction is cancelled.
+    ///
+    /// **Note:** A commission & a [`Royalty`] are included in each NFT resale,
+    /// so a seller's balance will be credited not with all fungible tokens but
+    /// with tokens with their deduction. A commission percentage can be
+    /// obtained by [`NFTPixelboardStateQuery::CommissionPercentage`].
     ///
     /// # Requirements
     /// * An NFT must be minted on a pixelboard.
     /// * [`msg::source()`](gstd::msg::source) must be the owner of an NFT.
     /// * `pixel_price` mustn't be more than [`MAX_PIXEL_PRICE`].
+    /// * `currency` must be [`None`] or an FT program accepted by
+    /// [`ConfigUpdate::AddCurrency`].
+    ///
+    /// * An NFT mustn't be on an English auction.
     ///
     /// On success, returns [`NFTPixelboardEvent::SaleStateChanged`].
     ChangeSaleState {
@@ -274,6 +664,9 @@ pub enum NFTPixelboardAction {
         /// the entire NFT, see the documentation of
         /// [`TokenInfo#structfield.pixel_price`].
         pixel_price: Option<u128>,
+        /// A [`Currency`] in which `pixel_price` is set. Ignored if
+        /// `pixel_price` is [`None`].
+        currency: Currency,
     },
 
     /// Paints with `painting` an NFT minted on a pixelboard.
@@ -291,6 +684,463 @@ pub enum NFTPixelboardAction {
         token_id: TokenId,
         painting: Vec<Color>,
     },
+
+    /// Starts an English auction of an NFT minted on a pixelboard.
+    ///
+    /// Transfers an NFT to a pixelboard program, where it stays until the
+    /// auction is settled by [`NFTPixelboardAction::SettleAuction`].
+    ///
+    /// # Requirements
+    /// * An NFT must be minted on a pixelboard.
+    /// * [`msg::source()`](gstd::msg::source) must be the owner of an NFT.
+    /// * An NFT mustn't be for sale or already on an auction.
+    /// * `min_pixel_price` mustn't be more than [`MAX_PIXEL_PRICE`].
+    /// * `duration` must be more than 0.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::AuctionStarted`].
+    StartAuction {
+        token_id: TokenId,
+        /// A minimum price of each pixel that an NFT occupies that a first bid
+        /// must offer.
+        min_pixel_price: u128,
+        /// A number of blocks during which bids will be accepted.
+        duration: BlockHeight,
+    },
+
+    /// Places a bid on an NFT auction.
+    ///
+    /// Transfers fungible tokens for the entire NFT from
+    /// [`msg::source()`] to a pixelboard program & credits fungible tokens of
+    /// a previous highest bidder to its balance. If an auction is settled or
+    /// outbid while fungible tokens are being transferred, they're credited to
+    /// [`msg::source()`]'s balance instead.
+    ///
+    /// # Requirements
+    /// * An NFT must be on an auction that isn't over.
+    /// * [`msg::source()`] mustn't be a seller of an NFT.
+    /// * `pixel_price` mustn't be less than
+    /// [`Auction#structfield.min_pixel_price`] & must be more than a pixel
+    /// price of a highest bid.
+    /// * `pixel_price` mustn't be more than [`MAX_PIXEL_PRICE`].
+    /// * [`msg::source()`] must have enough fungible tokens to pay for all
+    /// pixels that an NFT occupies with `pixel_price`.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::BidPlaced`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    Bid {
+        token_id: TokenId,
+        /// A price of each pixel that an NFT occupies.
+        pixel_price: u128,
+    },
+
+    /// Settles an NFT auction that is over.
+    ///
+    /// If there's a highest bid, transfers an NFT to a highest bidder & credits
+    /// a seller with a commission & royalty deduction in the same way as
+    /// [`NFTPixelboardAction::Buy`] does. Otherwise, transfers an NFT back to
+    /// a seller. If the transfer fails, an auction stays unsettled, so it can
+    /// be settled again.
+    ///
+    /// Can be sent by anyone.
+    ///
+    /// # Requirements
+    /// * An NFT must be on an auction that is over.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::AuctionSettled`].
+    SettleAuction(TokenId),
+
+    /// Puts an NFT minted on a pixelboard up for sale on a [`DutchAuction`].
+    ///
+    /// If an NFT is **not** for sale, then transfers it to a pixelboard
+    /// program. The NFT can then be bought by [`NFTPixelboardAction::Buy`] or
+    /// removed from sale by [`NFTPixelboardAction::ChangeSaleState`].
+    ///
+    /// Auctions & offers are held in the default currency of a pixelboard.
+    ///
+    /// # Requirements
+    /// * An NFT must be minted on a pixelboard.
+    /// * [`msg::source()`](gstd::msg::source) must be the owner of an NFT.
+    /// * An NFT mustn't be on an English auction.
+    /// * `start_pixel_price` mustn't be more than [`MAX_PIXEL_PRICE`].
+    /// * `floor_pixel_price` mustn't be more than `start_pixel_price`.
+    /// * `duration` must be more than 0.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::DutchAuctionStarted`].
+    StartDutchAuction {
+        token_id: TokenId,
+        /// A price of each pixel that an NFT occupies at the start.
+        start_pixel_price: u128,
+        /// A price of each pixel that an NFT occupies at the end.
+        floor_pixel_price: u128,
+        /// A number of blocks during which a pixel price falls.
+        duration: BlockHeight,
+    },
+
+    /// Makes an offer to buy an NFT minted on a pixelboard.
+    ///
+    /// Transfers fungible tokens for the entire NFT from [`msg::source()`] to a
+    /// pixelboard program, where they're held until the offer is accepted by
+    /// [`NFTPixelboardAction::AcceptOffer`] or cancelled by
+    /// [`NFTPixelboardAction::CancelOffer`]. If [`msg::source()`] has already
+    /// made an offer for an NFT, then the offer is replaced, and fungible
+    /// tokens of the previous one are credited to [`msg::source()`]'s balance.
+    ///
+    /// # Requirements
+    /// * An NFT must be minted on a pixelboard.
+    /// * [`msg::source()`] mustn't be the owner of an NFT.
+    /// * `pixel_price` mustn't be more than [`MAX_PIXEL_PRICE`].
+    /// * [`msg::source()`] must have enough fungible tokens to pay for all
+    /// pixels that an NFT occupies with `pixel_price`.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::OfferMade`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    MakeOffer {
+        token_id: TokenId,
+        /// An offered price of each pixel that an NFT occupies.
+        pixel_price: u128,
+    },
+
+    /// Cancels an offer of [`msg::source()`] for an NFT & credits its fungible
+    /// tokens to [`msg::source()`]'s balance. They can be withdrawn by
+    /// [`NFTPixelboardAction::Withdraw`].
+    ///
+    /// # Requirements
+    /// * [`msg::source()`] must have an offer for an NFT.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::OfferCancelled`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    CancelOffer(TokenId),
+
+    /// Accepts an offer for an NFT minted on a pixelboard.
+    ///
+    /// Transfers an NFT to `offerer` & credits [`msg::source()`] with a
+    /// commission & royalty deduction in the same way as
+    /// [`NFTPixelboardAction::Buy`] does. If an NFT is for sale, then it's
+    /// removed from sale.
+    ///
+    /// # Requirements
+    /// * An NFT must be minted on a pixelboard.
+    /// * [`msg::source()`] must be the owner of an NFT.
+    /// * An NFT mustn't be on an English auction.
+    /// * `offerer` must have an offer for an NFT.
+    /// * If an NFT isn't for sale, then a pixelboard program must be approved
+    /// to transfer it in an NFT program.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::OfferAccepted`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    AcceptOffer { token_id: TokenId, offerer: ActorId },
+
+    /// Transfers an NFT minted on a pixelboard to `to`.
+    ///
+    /// Transferring an NFT in an NFT program directly won't update its owner
+    /// on a pixelboard, so this action should be used instead.
+    ///
+    /// # Requirements
+    /// * An NFT must be minted on a pixelboard.
+    /// * [`msg::source()`](gstd::msg::source) must be the owner of an NFT.
+    /// * An NFT mustn't be for sale or on an auction.
+    /// * `to` address mustn't be [`ActorId::zero()`].
+    /// * A pixelboard program must be approved to transfer an NFT in an NFT
+    /// program.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::Transferred`].
+    Transfer { token_id: TokenId, to: ActorId },
+
+    /// Updates the owner of an NFT minted on a pixelboard to the one recorded
+    /// in an NFT program.
+    ///
+    /// Should be used after an NFT was transferred in an NFT program directly.
+    /// NFTs that are for sale or on an auction are held by a pixelboard
+    /// program, so their owners aren't changed.
+    ///
+    /// Can be sent by anyone.
+    ///
+    /// # Requirements
+    /// * An NFT must be minted on a pixelboard.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::OwnerSynced`].
+    SyncOwner(TokenId),
+
+    /// Does the same as [`NFTPixelboardAction::SyncOwner`] but for several
+    /// NFTs.
+    ///
+    /// # Requirements
+    /// * All NFTs must be minted on a pixelboard.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::OwnersSynced`].
+    SyncOwners(Vec<TokenId>),
+
+    /// Merges several NFTs minted on a pixelboard into one NFT which
+    /// [`Rectangle`] covers all their rectangles.
+    ///
+    /// Burns merged NFTs in an NFT program, mints a new one with
+    /// `token_metadata` & transfers it to [`msg::source()`]. Offers for merged
+    /// NFTs are credited to balances of offerers. A painting of merged NFTs
+    /// stays on a pixelboard. A merged NFT keeps a [`Royalty`] of merged ones.
+    /// If a step fails after one of merged NFTs is taken from
+    /// [`msg::source()`], the transaction stays pending until it's completed
+    /// by [`NFTPixelboardAction::ContinueTx`].
+    ///
+    /// # Requirements
+    /// * `token_ids` must contain at least 2 unique NFTs minted on a
+    /// pixelboard.
+    /// * [`msg::source()`] must be the owner of all NFTs.
+    /// * NFTs mustn't be for sale or on an auction.
+    /// * [`Rectangle`]s of NFTs must form one larger rectangle.
+    /// * NFTs must have the same [`Royalty`].
+    /// * A pixelboard program must be approved to transfer NFTs in an NFT
+    /// program.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::Merged`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    Merge {
+        token_ids: Vec<TokenId>,
+        token_metadata: TokenMetadata,
+    },
+
+    /// Splits an NFT minted on a pixelboard into 2 NFTs along `cut`.
+    ///
+    /// Burns a split NFT in an NFT program, mints 2 new ones with
+    /// `token_metadata` & transfers them to [`msg::source()`]. Offers for a
+    /// split NFT are credited to balances of offerers. A painting of a split
+    /// NFT stays on a pixelboard. New NFTs keep a [`Royalty`] of a split one.
+    /// If a step fails after a split NFT is taken from [`msg::source()`], the
+    /// transaction stays pending until it's completed by
+    /// [`NFTPixelboardAction::ContinueTx`].
+    ///
+    /// # Requirements
+    /// * An NFT must be minted on a pixelboard.
+    /// * [`msg::source()`] must be the owner of an NFT.
+    /// * An NFT mustn't be for sale or on an auction.
+    /// * `cut` must observe a block layout & lie strictly inside a [`Rectangle`]
+    /// of an NFT.
+    /// * A pixelboard program must be approved to transfer an NFT in an NFT
+    /// program.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::Split`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    Split {
+        token_id: TokenId,
+        cut: SplitLine,
+        /// Metadata of new NFTs: the first one is for the top or left part,
+        /// the second one is for the bottom or right part.
+        token_metadata: (TokenMetadata, TokenMetadata),
+    },
+
+    /// Burns an NFT minted on a pixelboard & returns its [`Rectangle`] to the
+    /// free territory of a pixelboard, so it can be minted again.
+    ///
+    /// Offers for a burned NFT are credited to balances of offerers. If
+    /// `restore_background` is `true`, a painting of a burned NFT is replaced
+    /// with the corresponding part of the initial `painting` from
+    /// [`InitNFTPixelboard`], otherwise it stays on a pixelboard. If burning
+    /// fails after an NFT is taken from [`msg::source()`], the transaction
+    /// stays pending until it's completed by
+    /// [`NFTPixelboardAction::ContinueTx`].
+    ///
+    /// # Requirements
+    /// * An NFT must be minted on a pixelboard.
+    /// * [`msg::source()`] must be the owner of an NFT.
+    /// * An NFT mustn't be for sale or on an auction.
+    /// * A pixelboard program must be approved to transfer an NFT in an NFT
+    /// program.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::Burned`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    Burn {
+        token_id: TokenId,
+        restore_background: bool,
+    },
+
+    /// Updates a configuration of a pixelboard that was set in
+    /// [`InitNFTPixelboard`].
+    ///
+    /// # Requirements
+    /// * [`msg::source()`] must be the owner of a pixelboard.
+    /// * A new value must satisfy requirements of a [`ConfigUpdate`] variant.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::PixelPriceUpdated`],
+    /// [`NFTPixelboardEvent::CommissionPercentageUpdated`],
+    /// [`NFTPixelboardEvent::FTProgramUpdated`],
+    /// [`NFTPixelboardEvent::CurrencyAdded`],
+    /// [`NFTPixelboardEvent::CurrencyRemoved`],
+    /// [`NFTPixelboardEvent::BeneficiariesUpdated`] or
+    /// [`NFTPixelboardEvent::PricingZonesUpdated`] depending on a
+    /// [`ConfigUpdate`] variant.
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    UpdateConfig(ConfigUpdate),
+
+    /// Proposes `ActorId` as a new owner of a pixelboard.
+    ///
+    /// The ownership (with all mint fees, commissions & admin rights) isn't
+    /// transferred until a proposed owner accepts it with
+    /// [`NFTPixelboardAction::AcceptOwnership`]. A new proposal replaces the
+    /// previous one, so a wrong proposal can be fixed by the current owner.
+    ///
+    /// # Requirements
+    /// * [`msg::source()`] must be the owner of a pixelboard.
+    /// * `ActorId` mustn't be [`ActorId::zero()`].
+    ///
+    /// On success, returns [`NFTPixelboardEvent::OwnerProposed`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    ProposeOwner(ActorId),
+
+    /// Accepts the ownership of a pixelboard proposed by
+    /// [`NFTPixelboardAction::ProposeOwner`].
+    ///
+    /// # Requirements
+    /// * [`msg::source()`] must be a proposed owner of a pixelboard.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::OwnershipAccepted`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    AcceptOwnership,
+
+    /// Pauses processing of all [`NFTPixelboardAction`]s of [`ActionKind`].
+    ///
+    /// Paused actions fail with [`NFTPixelboardError::Paused`] until they're
+    /// unpaused by [`NFTPixelboardAction::Unpause`].
+    ///
+    /// # Requirements
+    /// * [`msg::source()`] must be the owner of a pixelboard.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::Paused`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    Pause(ActionKind),
+
+    /// Resumes processing of [`NFTPixelboardAction`]s of [`ActionKind`]
+    /// paused by [`NFTPixelboardAction::Pause`].
+    ///
+    /// # Requirements
+    /// * [`msg::source()`] must be the owner of a pixelboard.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::Unpaused`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    Unpause(ActionKind),
+
+    /// Continues a pending transaction of [`msg::source()`] that was
+    /// interrupted.
+    ///
+    /// Replays a stored [`NFTPixelboardAction`] with its stored
+    /// [`TransactionId`], so already completed steps of the transaction aren't
+    /// repeated in FT & NFT programs.
+    ///
+    /// # Requirements
+    /// * [`msg::source()`] must have a pending transaction.
+    /// * A pending transaction mustn't be in progress, unless it was started
+    /// at least [`TX_TIMEOUT`] blocks ago.
+    ///
+    /// On success, returns an [`NFTPixelboardEvent`] of a pending
+    /// [`NFTPixelboardAction`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    ContinueTx,
+
+    /// Cancels a pending transaction of `ActorId` & refunds fungible tokens
+    /// held by a pixelboard in it.
+    ///
+    /// Should be used only for transactions that were interrupted & can't be
+    /// completed by [`NFTPixelboardAction::ContinueTx`].
+    ///
+    /// # Requirements
+    /// * [`msg::source()`] must be the owner of a pixelboard.
+    /// * `ActorId` must have a pending transaction.
+    /// * A pending transaction mustn't be in progress, unless it was started
+    /// at least [`TX_TIMEOUT`] blocks ago.
+    /// * A pending transaction mustn't be one of [`NFTPixelboardAction::Merge`],
+    /// [`NFTPixelboardAction::Split`] & [`NFTPixelboardAction::Burn`] that
+    /// already took NFTs from `ActorId`, or [`NFTPixelboardAction::MintBatch`]
+    /// that already delivered NFTs to it.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::TxCancelled`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    CancelTx(ActorId),
+
+    /// Withdraws `amount` of [`msg::source()`]'s balance in `currency`.
+    ///
+    /// Sellers, the owner of a pixelboard, outbid bidders & offerers of
+    /// disposed NFTs or replaced & cancelled offers are credited instead of
+    /// being paid directly, so a failed payment to one of them can't block
+    /// trading.
+    ///
+    /// # Requirements
+    /// * [`msg::source()`] must have at least `amount` on its balance in
+    /// `currency`. This can be found out by
+    /// [`NFTPixelboardState#structfield.balances`].
+    ///
+    /// On success, returns [`NFTPixelboardEvent::Withdrawn`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    Withdraw {
+        /// A [`Currency`] to withdraw in.
+        currency: Currency,
+        amount: u128,
+    },
+
+    /// Mints several NFTs on a pixelboard with given [`Rectangle`]s,
+    /// [`TokenMetadata`] & paintings in one transaction.
+    ///
+    /// All NFTs are paid with one payment in the default currency & get the
+    /// default [`Royalty`] percentage. Pixel prices are calculated as if none
+    /// of the NFTs were minted yet. Either all NFTs are minted & transferred
+    /// to [`msg::source()`], or none, and the payment is refunded. If a
+    /// transfer fails after some NFTs were delivered, the transaction stays
+    /// pending with the payment until it's completed by
+    /// [`NFTPixelboardAction::ContinueTx`] & can't be cancelled.
+    ///
+    /// # Requirements
+    /// * The batch mustn't be empty.
+    /// * Each [`Rectangle`] & painting must meet requirements of
+    /// [`NFTPixelboardAction::Mint`].
+    /// * [`Rectangle`]s mustn't collide with each other.
+    /// * [`msg::source()`] must have enough fungible tokens to buy all free
+    /// pixels of all [`Rectangle`]s. See [`NFTPixelboardAction::Mint`] for
+    /// details.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::MintedBatch`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    MintBatch(Vec<(Rectangle, TokenMetadata, Vec<Color>)>),
+
+    /// Buys several NFTs minted on a pixelboard in one transaction.
+    ///
+    /// NFTs that aren't for sale in `currency` are skipped. A total price of
+    /// the rest is held by a pixelboard program at once, and then each NFT is
+    /// bought as by [`NFTPixelboardAction::Buy`]. If an NFT is no longer for
+    /// sale at its price or its transfer fails, then its price is credited
+    /// back to a balance of [`msg::source()`]. If none of NFTs is bought, the
+    /// total price is refunded & the transaction fails with
+    /// [`NFTPixelboardError::NFTTransferFailed`].
+    ///
+    /// # Requirements
+    /// * At least one of NFTs must be for sale in `currency`.
+    /// * A total price of NFTs for sale mustn't be more than
+    /// `max_total_price`.
+    /// * [`msg::source()`] must have enough fungible tokens to pay the total
+    /// price. If a pixelboard uses [`PaymentMode::Value`], then these tokens
+    /// must be attached to a message as a value.
+    ///
+    /// On success, returns [`NFTPixelboardEvent::BoughtBatch`].
+    ///
+    /// [`msg::source()`]: gstd::msg::source
+    BuyBatch {
+        token_ids: Vec<TokenId>,
+        max_total_price: u128,
+        /// A [`Currency`] to pay with.
+        currency: Currency,
+    },
 }
 
 /// A result of processed [`NFTPixelboardAction`] in case of successfull execution.
@@ -304,6 +1154,79 @@ pub enum NFTPixelboardEvent {
     SaleStateChanged(TokenId),
     /// Should be returned from [`NFTPixelboardAction::Paint`].
     Painted(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::StartAuction`].
+    AuctionStarted(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::Bid`].
+    BidPlaced(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::SettleAuction`].
+    AuctionSettled(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::StartDutchAuction`].
+    DutchAuctionStarted(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::MakeOffer`].
+    OfferMade(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::CancelOffer`].
+    OfferCancelled(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::AcceptOffer`].
+    OfferAccepted(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::Transfer`].
+    Transferred(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::SyncOwner`].
+    OwnerSynced(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::SyncOwners`].
+    ///
+    /// Contains NFTs which owners were changed.
+    OwnersSynced(Vec<TokenId>),
+    /// Should be returned from [`NFTPixelboardAction::Merge`].
+    ///
+    /// Contains an id of a new NFT.
+    Merged(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::Split`].
+    ///
+    /// Contains ids of new NFTs in the same order as `token_metadata` in
+    /// [`NFTPixelboardAction::Split`].
+    Split(TokenId, TokenId),
+    /// Should be returned from [`NFTPixelboardAction::Burn`].
+    Burned(TokenId),
+    /// Should be returned from [`NFTPixelboardAction::UpdateConfig`] with
+    /// [`ConfigUpdate::PixelPrice`].
+    PixelPriceUpdated(u128),
+    /// Should be returned from [`NFTPixelboardAction::UpdateConfig`] with
+    /// [`ConfigUpdate::CommissionPercentage`].
+    CommissionPercentageUpdated(u8),
+    /// Should be returned from [`NFTPixelboardAction::UpdateConfig`] with
+    /// [`ConfigUpdate::FTProgram`].
+    FTProgramUpdated(ActorId),
+    /// Should be returned from [`NFTPixelboardAction::UpdateConfig`] with
+    /// [`ConfigUpdate::AddCurrency`].
+    CurrencyAdded(ActorId),
+    /// Should be returned from [`NFTPixelboardAction::UpdateConfig`] with
+    /// [`ConfigUpdate::RemoveCurrency`].
+    CurrencyRemoved(ActorId),
+    /// Should be returned from [`NFTPixelboardAction::UpdateConfig`] with
+    /// [`ConfigUpdate::Beneficiaries`].
+    BeneficiariesUpdated,
+    /// Should be returned from [`NFTPixelboardAction::UpdateConfig`] with
+    /// [`ConfigUpdate::PricingZones`].
+    PricingZonesUpdated,
+    /// Should be returned from [`NFTPixelboardAction::ProposeOwner`].
+    OwnerProposed(ActorId),
+    /// Should be returned from [`NFTPixelboardAction::AcceptOwnership`].
+    ///
+    /// Contains an address of a new owner.
+    OwnershipAccepted(ActorId),
+    /// Should be returned from [`NFTPixelboardAction::Pause`].
+    Paused(ActionKind),
+    /// Should be returned from [`NFTPixelboardAction::Unpause`].
+    Unpaused(ActionKind),
+    /// Should be returned from [`NFTPixelboardAction::CancelTx`].
+    TxCancelled(ActorId),
+    /// Should be returned from [`NFTPixelboardAction::Withdraw`].
+    Withdrawn(u128),
+    /// Should be returned from [`NFTPixelboardAction::MintBatch`].
+    MintedBatch(Vec<TokenId>),
+    /// Should be returned from [`NFTPixelboardAction::BuyBatch`] with IDs of
+    /// bought NFTs.
+    BoughtBatch(Vec<TokenId>),
 }
 
 /// A result of processed [`NFTPixelboardAction`] in case of failure.
@@ -328,4 +1251,35 @@ pub enum NFTPixelboardError {
     NFTTransferFailed,
     FTokensTransferFailed,
     NFTMintFailed,
+    NFTIsOnSale,
+    NFTIsOnAuction,
+    NFTIsNotOnAuction,
+    ZeroDuration,
+    AuctionIsOver,
+    AuctionIsNotOver,
+    BidTooLow,
+    WrongFloorPixelPrice,
+    OwnNFT,
+    OfferNotFound,
+    NFTOwnerRequestFailed,
+    NFTBurnFailed,
+    NotEnoughNFTs,
+    RectanglesNotFormRectangle,
+    WrongSplitLine,
+    FundsInEscrow,
+    NotProposedOwner,
+    Paused,
+    TxNotFound,
+    NotEnoughValue,
+    ValueTransferFailed,
+    UnknownCurrency,
+    WrongCurrency,
+    InsufficientBalance,
+    WrongRoyaltyPercentage,
+    RoyaltiesMismatch,
+    WrongBeneficiaryShares,
+    ZonesOverlap,
+    TotalPriceExceeded,
+    TxInProgress,
+    TxNotCancellable,
 }
@@ -1,9 +1,63 @@
-use crate::utils;
+use crate::{occupancy::Occupancy, utils};
 use gear_lib::non_fungible_token::token::{TokenId, TokenMetadata};
 use gstd::{async_main, errors::Result as GstdResult, exec, msg, prelude::*, ActorId, MessageId};
 use nft_pixelboard_io::*;
 pub const MIN_STEP_FOR_TX: u64 = 3;
 
+// Returns a number of transaction ids that `action` can take.
+fn tx_step(action: &NFTPixelboardAction) -> u64 {
+    match action {
+        // 2 for burning each NFT & 2 for minting a merged one.
+        NFTPixelboardAction::Merge { token_ids, .. } => {
+            (token_ids.len() as u64 * 2 + 2).max(MIN_STEP_FOR_TX)
+        }
+        // 2 for burning a split NFT & 2 for minting each new one.
+        NFTPixelboardAction::Split { .. } => 6,
+        // 1 for transferring each NFT.
+        NFTPixelboardAction::BuyBatch { token_ids, .. } => {
+            (token_ids.len() as u64).max(MIN_STEP_FOR_TX)
+        }
+        // 2 for minting & transferring each NFT & 1 for burning it on a
+        // rollback.
+        NFTPixelboardAction::MintBatch(tokens) => (tokens.len() as u64 * 3).max(MIN_STEP_FOR_TX),
+        _ => MIN_STEP_FOR_TX,
+    }
+}
+
+// Returns a kind of `action` or `None` if `action` can't be paused.
+fn action_kind(action: &NFTPixelboardAction) -> Option<ActionKind> {
+    match action {
+        NFTPixelboardAction::Mint { .. } | NFTPixelboardAction::MintBatch(_) => {
+            Some(ActionKind::Minting)
+        }
+        NFTPixelboardAction::Buy { .. }
+        | NFTPixelboardAction::BuyBatch { .. }
+        | NFTPixelboardAction::ChangeSaleState { .. }
+        | NFTPixelboardAction::StartAuction { .. }
+        | NFTPixelboardAction::Bid { .. }
+        | NFTPixelboardAction::SettleAuction(_)
+        | NFTPixelboardAction::StartDutchAuction { .. }
+        | NFTPixelboardAction::MakeOffer { .. }
+        | NFTPixelboardAction::CancelOffer(_)
+        | NFTPixelboardAction::AcceptOffer { .. } => Some(ActionKind::Trading),
+        NFTPixelboardAction::Paint { .. } => Some(ActionKind::Painting),
+        NFTPixelboardAction::Transfer { .. }
+        | NFTPixelboardAction::SyncOwner(_)
+        | NFTPixelboardAction::SyncOwners(_)
+        | NFTPixelboardAction::Merge { .. }
+        | NFTPixelboardAction::Split { .. }
+        | NFTPixelboardAction::Burn { .. } => Some(ActionKind::Transferring),
+        NFTPixelboardAction::UpdateConfig(_)
+        | NFTPixelboardAction::ProposeOwner(_)
+        | NFTPixelboardAction::AcceptOwnership
+        | NFTPixelboardAction::Pause(_)
+        | NFTPixelboardAction::Unpause(_)
+        | NFTPixelboardAction::ContinueTx
+        | NFTPixelboardAction::CancelTx(_)
+        | NFTPixelboardAction::Withdraw { .. } => None,
+    }
+}
+
 fn get_pixel_count<P: Into<usize>>(width: P, height: P) -> Result<usize, NFTPixelboardError> {
     let pixel_count = width.into() * height.into();
     if pixel_count == 0 {
@@ -26,6 +80,107 @@ fn check_pixel_price(pixel_price: u128) -> Result<(), NFTPixelboardError> {
     Ok(())
 }
 
+fn check_commission_percentage(commission_percentage: u8) -> Result<(), NFTPixelboardError> {
+    if commission_percentage > 100 {
+        return Err(NFTPixelboardError::WrongCommissionPercentage);
+    }
+    Ok(())
+}
+
+fn check_royalty_percentage(royalty_percentage: u8) -> Result<(), NFTPixelboardError> {
+    if royalty_percentage > 100 {
+        return Err(NFTPixelboardError::WrongRoyaltyPercentage);
+    }
+    Ok(())
+}
+
+fn check_beneficiaries(beneficiaries: &[(ActorId, u8)]) -> Result<(), NFTPixelboardError> {
+    if !beneficiaries.is_empty()
+        && beneficiaries
+            .iter()
+            .map(|(_, share)| *share as u32)
+            .sum::<u32>()
+            != 100
+    {
+        return Err(NFTPixelboardError::WrongBeneficiaryShares);
+    }
+    beneficiaries
+        .iter()
+        .try_for_each(|(beneficiary, _)| check_address(beneficiary))
+}
+
+fn check_pricing_zones(
+    zones: &[PricingZone],
+    resolution: Resolution,
+) -> Result<(), NFTPixelboardError> {
+    for (index, zone) in zones.iter().enumerate() {
+        let rectangle = zone.rectangle;
+
+        if rectangle.top_left_corner.x > rectangle.bottom_right_corner.x
+            || rectangle.top_left_corner.y > rectangle.bottom_right_corner.y
+        {
+            return Err(NFTPixelboardError::CoordinatesWithWrongCorners);
+        }
+        if rectangle.bottom_right_corner.x > resolution.width
+            || rectangle.bottom_right_corner.y > resolution.height
+        {
+            return Err(NFTPixelboardError::CoordinatesOutOfCanvas);
+        }
+        check_pixel_price(zone.pixel_price)?;
+
+        if zones[..index]
+            .iter()
+            .any(|other| other.rectangle.intersection(&rectangle).is_some())
+        {
+            return Err(NFTPixelboardError::ZonesOverlap);
+        }
+    }
+    Ok(())
+}
+
+fn check_address(address: &ActorId) -> Result<(), NFTPixelboardError> {
+    if *address == ActorId::zero() {
+        return Err(NFTPixelboardError::ZeroAddress);
+    }
+    Ok(())
+}
+
+// get_pixel_count() isn't used here because it checks an NFT area for equality
+// to 0, but an area of a minted NFT is always not equal 0.
+fn get_token_price(rectangle: &Rectangle, pixel_price: u128) -> u128 {
+    (rectangle.width() as usize * rectangle.height() as usize) as u128 * pixel_price
+}
+
+// Splits `token_price` into a resale commission, a royalty & a seller's share.
+fn split_resale_price(
+    token_price: u128,
+    commission_percentage: u8,
+    royalty_percentage: u8,
+) -> (u128, u128, u128) {
+    let commission = token_price * commission_percentage as u128 / 100;
+    let royalty = (token_price * royalty_percentage as u128 / 100).min(token_price - commission);
+
+    (commission, royalty, token_price - commission - royalty)
+}
+
+fn check_bid(auction: &Auction, pixel_price: u128) -> Result<(), NFTPixelboardError> {
+    if exec::block_height() >= auction.ends_at {
+        return Err(NFTPixelboardError::AuctionIsOver);
+    }
+
+    if pixel_price < auction.min_pixel_price {
+        return Err(NFTPixelboardError::BidTooLow);
+    }
+
+    if let Some((_, highest_pixel_price)) = auction.highest_bid {
+        if pixel_price <= highest_pixel_price {
+            return Err(NFTPixelboardError::BidTooLow);
+        }
+    }
+
+    Ok(())
+}
+
 fn get_mut_token<'a>(
     rectangles: &'a BTreeMap<TokenId, Rectangle>,
     tokens: &'a mut BTreeMap<Rectangle, TokenInfo>,
@@ -44,6 +199,22 @@ fn get_mut_token<'a>(
     Ok((rectangle, tokens))
 }
 
+fn get_bounding_rectangle(rectangles: &[Rectangle]) -> Rectangle {
+    let mut bounding_rectangle = rectangles[0];
+
+    for rectangle in &rectangles[1..] {
+        let top_left_corner = &mut bounding_rectangle.top_left_corner;
+        let bottom_right_corner = &mut bounding_rectangle.bottom_right_corner;
+
+        top_left_corner.x = top_left_corner.x.min(rectangle.top_left_corner.x);
+        top_left_corner.y = top_left_corner.y.min(rectangle.top_left_corner.y);
+        bottom_right_corner.x = bottom_right_corner.x.max(rectangle.bottom_right_corner.x);
+        bottom_right_corner.y = bottom_right_corner.y.max(rectangle.bottom_right_corner.y);
+    }
+
+    bounding_rectangle
+}
+
 fn paint(
     canvas_resolution: Resolution,
     rectangle: &Rectangle,
@@ -73,184 +244,1419 @@ fn paint(
 #[derive(Default)]
 pub struct NFTPixelboard {
     pub owner: ActorId,
+    pub pending_owner: Option<ActorId>,
     pub block_side_length: BlockSideLength,
     pub pixel_price: u128,
     pub resolution: Resolution,
     pub commission_percentage: u8,
+    pub royalty_percentage: u8,
     pub painting: Vec<Color>,
+    pub background: Vec<Color>,
+
+    pub rectangles_by_token_ids: BTreeMap<TokenId, Rectangle>,
+    pub tokens_by_rectangles: BTreeMap<Rectangle, TokenInfo>,
+    pub occupancy: Occupancy,
+    // A total area of rectangles in `tokens_by_rectangles`.
+    pub minted_area: u32,
+    pub royalties: BTreeMap<TokenId, Royalty>,
+    pub beneficiaries: Vec<(ActorId, u8)>,
+    pub pricing_zones: Vec<PricingZone>,
+    pub pricing_curve: Option<PricingCurve>,
+
+    pub ft_program: ActorId,
+    pub nft_program: ActorId,
+    pub payment_mode: PaymentMode,
+    pub currencies: BTreeMap<ActorId, u128>,
+
+    pub txs: BTreeMap<ActorId, (TransactionId, NFTPixelboardAction)>,
+    pub tx_id: TransactionId,
+    pub escrows: BTreeMap<ActorId, u128>,
+    // Block heights at which transactions that are being processed were
+    // started. An entry stays if processing is interrupted.
+    pub txs_in_progress: BTreeMap<ActorId, BlockHeight>,
+    // Users whose transactions failed after their NFTs were taken by the
+    // program or some minted NFTs were delivered to them, so the transactions
+    // can only be completed.
+    pub unfinished_txs: BTreeSet<ActorId>,
+    // Values attached to messages that haven't been spent yet.
+    pub deposits: BTreeMap<ActorId, u128>,
+    pub balances: BTreeMap<ActorId, BTreeMap<Currency, u128>>,
+
+    pub auctions: BTreeMap<TokenId, Auction>,
+    pub dutch_auctions: BTreeMap<TokenId, DutchAuction>,
+    pub offers: BTreeMap<TokenId, BTreeMap<ActorId, u128>>,
+
+    pub paused: BTreeSet<ActionKind>,
+}
+
+impl NFTPixelboard {
+    async fn mint(
+        &mut self,
+        tx_id: TransactionId,
+        rectangle: Rectangle,
+        token_metadata: TokenMetadata,
+        painting: Vec<Color>,
+        currency: Currency,
+        royalty_percentage: Option<u8>,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let royalty = Royalty {
+            minter: msg_source,
+            percentage: royalty_percentage.unwrap_or(self.royalty_percentage),
+        };
+        check_royalty_percentage(royalty.percentage)?;
+        let rectangle_width = rectangle.width() as usize;
+        let rectangle_height = rectangle.height() as usize;
+        get_pixel_count(rectangle_width, rectangle_height)?;
+        let token_price =
+            self.mint_price(&rectangle, currency, self.minted_percentage(&[rectangle]))?;
+
+        // Reservation: a rectangle reserved by an interrupted attempt of this
+        // transaction has already been checked.
+        if !self.is_reserved_by(&rectangle, &msg_source) {
+            self.coordinates_check(rectangle, painting.clone())?;
+            self.occupy(
+                rectangle,
+                TokenInfo {
+                    owner: msg_source,
+                    pixel_price: None,
+                    token_id: None,
+                    currency: None,
+                },
+            );
+        }
+
+        // Payment: transfer to contract account
+        let token_price = match self
+            .escrow_payment(tx_id, currency, msg_source, token_price)
+            .await
+        {
+            Ok(token_price) => token_price,
+            Err(error) => {
+                self.vacate(&rectangle);
+                return Err(error);
+            }
+        };
+
+        let token_id = match utils::mint_nft(tx_id, &self.nft_program, token_metadata).await {
+            Ok(token_id) => token_id,
+            Err(error) => {
+                self.rollback_mint(tx_id, currency, &[rectangle], &[])
+                    .await?;
+                return Err(error);
+            }
+        };
+        if let Err(error) = utils::transfer_nft(
+            tx_id.wrapping_add(1),
+            &self.nft_program,
+            &msg_source,
+            token_id,
+        )
+        .await
+        {
+            self.rollback_mint(tx_id, currency, &[rectangle], &[token_id])
+                .await?;
+            return Err(error);
+        }
+
+        // Commitment: painting & insertion
+        paint(
+            self.resolution,
+            &rectangle,
+            rectangle_width,
+            rectangle_height,
+            &mut self.painting,
+            painting,
+        );
+        self.insert_token(token_id, rectangle, msg_source, Some(royalty));
+        self.escrows.remove(&msg_source);
+        self.credit_beneficiaries(currency, token_price);
+
+        Ok(NFTPixelboardEvent::Minted(token_id))
+    }
+
+    async fn mint_batch(
+        &mut self,
+        tx_id: TransactionId,
+        tokens: Vec<(Rectangle, TokenMetadata, Vec<Color>)>,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        if tokens.is_empty() {
+            return Err(NFTPixelboardError::NotEnoughNFTs);
+        }
+        let rectangles: Vec<_> = tokens.iter().map(|(rectangle, ..)| *rectangle).collect();
+        let minted_percentage = self.minted_percentage(&rectangles);
+
+        // Reservation: rectangles are reserved together, so they collide
+        // neither with existing ones nor with each other.
+        if !rectangles
+            .iter()
+            .all(|rectangle| self.is_reserved_by(rectangle, &msg_source))
+        {
+            for (index, (rectangle, _, painting)) in tokens.iter().enumerate() {
+                if let Err(error) = self.coordinates_check(*rectangle, painting.clone()) {
+                    for rectangle in &rectangles[..index] {
+                        self.vacate(rectangle);
+                    }
+                    return Err(error);
+                }
+                self.occupy(
+                    *rectangle,
+                    TokenInfo {
+                        owner: msg_source,
+                        pixel_price: None,
+                        token_id: None,
+                        currency: None,
+                    },
+                );
+            }
+        }
+
+        // Reserved rectangles don't overlap, so their total price can't
+        // overflow.
+        let price = rectangles
+            .iter()
+            .map(|rectangle| {
+                mint_price(
+                    rectangle,
+                    self.pixel_price,
+                    &self.pricing_zones,
+                    self.pricing_curve,
+                    minted_percentage,
+                )
+            })
+            .sum();
+
+        // Payment: one transfer for all NFTs
+        let price = match self.escrow_payment(tx_id, None, msg_source, price).await {
+            Ok(price) => price,
+            Err(error) => {
+                for rectangle in &rectangles {
+                    self.vacate(rectangle);
+                }
+                return Err(error);
+            }
+        };
+
+        // NFTs are transferred only after all of them are minted, so a failed
+        // mint can be rolled back by burning NFTs still held by the program.
+        let mut token_ids = Vec::with_capacity(tokens.len());
+        let transfer_tx_id = tx_id.wrapping_add(tokens.len() as u64);
+
+        for (index, (_, token_metadata, _)) in tokens.iter().enumerate() {
+            match utils::mint_nft(
+                tx_id.wrapping_add(index as u64),
+                &self.nft_program,
+                token_metadata.clone(),
+            )
+            .await
+            {
+                Ok(token_id) => token_ids.push(token_id),
+                Err(error) => {
+                    self.rollback_mint(tx_id, None, &rectangles, &token_ids)
+                        .await?;
+                    return Err(error);
+                }
+            }
+        }
+        for (index, token_id) in token_ids.iter().enumerate() {
+            if let Err(error) = utils::transfer_nft(
+                transfer_tx_id.wrapping_add(index as u64),
+                &self.nft_program,
+                &msg_source,
+                *token_id,
+            )
+            .await
+            {
+                // Delivered NFTs can't be burned, so the batch stays pending
+                // with its payment until `ContinueTx` repeats the remaining
+                // transfers with the same transaction ids.
+                if index == 0 {
+                    self.rollback_mint(tx_id, None, &rectangles, &token_ids)
+                        .await?;
+                } else {
+                    self.unfinished_txs.insert(msg_source);
+                }
+                return Err(error);
+            }
+        }
+
+        // Commitment: painting & insertion
+        let royalty = Royalty {
+            minter: msg_source,
+            percentage: self.royalty_percentage,
+        };
+
+        for ((rectangle, _, painting), token_id) in tokens.into_iter().zip(&token_ids) {
+            paint(
+                self.resolution,
+                &rectangle,
+                rectangle.width() as usize,
+                rectangle.height() as usize,
+                &mut self.painting,
+                painting,
+            );
+            self.insert_token(*token_id, rectangle, msg_source, Some(royalty));
+        }
+        self.escrows.remove(&msg_source);
+        self.credit_beneficiaries(None, price);
+
+        Ok(NFTPixelboardEvent::MintedBatch(token_ids))
+    }
+
+    // Checks whether `rectangle` is reserved by a mint of `owner` that hasn't
+    // been completed.
+    fn is_reserved_by(&self, rectangle: &Rectangle, owner: &ActorId) -> bool {
+        self.tokens_by_rectangles
+            .get(rectangle)
+            .map_or(false, |token| {
+                token.owner == *owner && token.token_id.is_none()
+            })
+    }
+
+    // Burns minted NFTs, releases reserved rectangles & refunds a payment.
+    // Burning takes 1 transaction id per rectangle after 2 ids per rectangle
+    // taken by minting & transferring.
+    async fn rollback_mint(
+        &mut self,
+        tx_id: TransactionId,
+        currency: Currency,
+        rectangles: &[Rectangle],
+        token_ids: &[TokenId],
+    ) -> Result<(), NFTPixelboardError> {
+        let msg_source = msg::source();
+        let burn_tx_id = tx_id.wrapping_add(rectangles.len() as u64 * 2);
+
+        for (index, token_id) in token_ids.iter().enumerate() {
+            utils::burn_nft(
+                burn_tx_id.wrapping_add(index as u64),
+                &self.nft_program,
+                *token_id,
+            )
+            .await?;
+        }
+        for rectangle in rectangles {
+            self.vacate(rectangle);
+        }
+
+        if let Some(escrow) = self.escrows.get(&msg_source).copied() {
+            self.send_payment(tx_id.wrapping_add(1), currency, &msg_source, escrow)
+                .await?;
+            self.escrows.remove(&msg_source);
+        }
+
+        Ok(())
+    }
+
+    async fn buy(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+        currency: Currency,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+
+        // Escrow: the entire price is held until the NFT is delivered. A
+        // continued purchase reuses an escrow of its interrupted attempt.
+        let (seller, escrow) = match self.escrows.get(&msg_source).copied() {
+            Some(escrow) => (None, escrow),
+            None => {
+                let (_, seller, token_price) = self.sale(token_id, currency)?;
+                let escrow = self
+                    .escrow_payment(tx_id, currency, msg_source, token_price)
+                    .await?;
+
+                (Some(seller), escrow)
+            }
+        };
+
+        // The NFT could be sold, relisted or repriced while the payment was
+        // being taken or before the purchase is continued.
+        let (rectangle, seller, token_price) = match self.sale(token_id, currency) {
+            Ok((rectangle, current_seller, token_price))
+                if seller.map_or(true, |seller| seller == current_seller)
+                    && token_price <= escrow =>
+            {
+                (rectangle, current_seller, token_price)
+            }
+            _ => {
+                self.send_payment(tx_id.wrapping_add(1), currency, &msg_source, escrow)
+                    .await?;
+                self.escrows.remove(&msg_source);
+
+                return Err(NFTPixelboardError::NFTIsNotOnSale);
+            }
+        };
+
+        if let Err(error) =
+            utils::transfer_nft(tx_id, &self.nft_program, &msg_source, token_id).await
+        {
+            self.send_payment(tx_id.wrapping_add(1), currency, &msg_source, escrow)
+                .await?;
+            self.escrows.remove(&msg_source);
+
+            return Err(error);
+        }
+
+        self.complete_sale(token_id, &rectangle, msg_source);
+        self.escrows.remove(&msg_source);
+
+        // A price on a Dutch auction could fall since the escrow was taken.
+        self.credit(msg_source, currency, escrow - token_price);
+        self.credit_resale(token_id, seller, currency, token_price);
+
+        Ok(NFTPixelboardEvent::Bought(token_id))
+    }
+
+    async fn buy_batch(
+        &mut self,
+        tx_id: TransactionId,
+        token_ids: Vec<TokenId>,
+        max_total_price: u128,
+        currency: Currency,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let mut unique_token_ids = BTreeSet::new();
+        // Indexes are kept, so a retry of an interrupted transaction transfers
+        // each NFT with the same transaction id.
+        let sales: Vec<_> = token_ids
+            .into_iter()
+            .filter(|token_id| unique_token_ids.insert(*token_id))
+            .enumerate()
+            .filter_map(|(index, token_id)| {
+                self.sale(token_id, currency)
+                    .ok()
+                    .map(|sale| (index, token_id, sale))
+            })
+            .collect();
+        if sales.is_empty() {
+            return Err(NFTPixelboardError::NFTIsNotOnSale);
+        }
+        let total_price = sales
+            .iter()
+            .try_fold(0u128, |total_price, (_, _, (_, _, token_price))| {
+                total_price.checked_add(*token_price)
+            })
+            .filter(|total_price| *total_price <= max_total_price)
+            .ok_or(NFTPixelboardError::TotalPriceExceeded)?;
+
+        // Escrow: the total price is held until NFTs are delivered. Prices of
+        // bought NFTs are deducted from it one by one, so an interrupted
+        // transaction is continued with the remainder.
+        let mut escrow = self
+            .escrow_payment(tx_id, currency, msg_source, total_price)
+            .await?;
+        let mut bought = vec![];
+
+        for (index, token_id, (rectangle, seller, escrowed_price)) in sales {
+            // An NFT could be sold or relisted while previous ones were being
+            // transferred, and its price on a Dutch auction could fall.
+            let token_price = match self.sale(token_id, currency) {
+                Ok((current_rectangle, current_seller, token_price))
+                    if current_rectangle == rectangle
+                        && current_seller == seller
+                        && token_price <= escrowed_price
+                        && token_price <= escrow =>
+                {
+                    token_price
+                }
+                _ => continue,
+            };
+
+            if utils::transfer_nft(
+                tx_id.wrapping_add(index as u64),
+                &self.nft_program,
+                &msg_source,
+                token_id,
+            )
+            .await
+            .is_err()
+            {
+                continue;
+            }
+
+            self.complete_sale(token_id, &rectangle, msg_source);
+            self.credit_resale(token_id, seller, currency, token_price);
+            escrow -= token_price;
+            self.escrows.insert(msg_source, escrow);
+            bought.push(token_id);
+        }
+
+        // Nothing was bought, so the payment is refunded at once instead of
+        // being credited.
+        if bought.is_empty() {
+            self.send_payment(tx_id.wrapping_add(1), currency, &msg_source, escrow)
+                .await?;
+            self.escrows.remove(&msg_source);
+
+            return Err(NFTPixelboardError::NFTTransferFailed);
+        }
+
+        self.escrows.remove(&msg_source);
+        self.credit(msg_source, currency, escrow);
+
+        Ok(NFTPixelboardEvent::BoughtBatch(bought))
+    }
+
+    // Returns a rectangle, a seller & a price of an NFT that's for sale in
+    // `currency`.
+    fn sale(
+        &self,
+        token_id: TokenId,
+        currency: Currency,
+    ) -> Result<(Rectangle, ActorId, u128), NFTPixelboardError> {
+        let rectangle = self
+            .rectangles_by_token_ids
+            .get(&token_id)
+            .ok_or(NFTPixelboardError::NFTNotFoundById)?;
+        let token = self
+            .tokens_by_rectangles
+            .get(rectangle)
+            .ok_or(NFTPixelboardError::NFTNotFountByRectangle)?;
+
+        let pixel_price = if let Some(dutch_auction) = self.dutch_auctions.get(&token_id) {
+            dutch_auction.pixel_price(exec::block_height())
+        } else if let Some(pixel_price) = token.pixel_price {
+            pixel_price
+        } else {
+            return Err(NFTPixelboardError::NFTIsNotOnSale);
+        };
+        if currency != token.currency {
+            return Err(NFTPixelboardError::WrongCurrency);
+        }
+
+        Ok((
+            *rectangle,
+            token.owner,
+            get_token_price(rectangle, pixel_price),
+        ))
+    }
+
+    // Hands a sold NFT over to `buyer` & takes it off sale.
+    fn complete_sale(&mut self, token_id: TokenId, rectangle: &Rectangle, buyer: ActorId) {
+        if let Some(token) = self.tokens_by_rectangles.get_mut(rectangle) {
+            token.pixel_price = None;
+            token.currency = None;
+            token.owner = buyer;
+        }
+        self.dutch_auctions.remove(&token_id);
+    }
+
+    async fn change_sale_state(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+        pixel_price: Option<u128>,
+        currency: Currency,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        if pixel_price.is_some() {
+            self.currency_pixel_price(currency)?;
+        }
+        let (_, token) = get_mut_token(
+            &self.rectangles_by_token_ids,
+            &mut self.tokens_by_rectangles,
+            token_id,
+        )?;
+        if token.owner != msg_source {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+        if self.auctions.contains_key(&token_id) {
+            return Err(NFTPixelboardError::NFTIsOnAuction);
+        }
+
+        if let Some(price) = pixel_price {
+            check_pixel_price(price)?;
+            if token.pixel_price.is_none() {
+                utils::transfer_nft(tx_id, &self.nft_program, &exec::program_id(), token_id)
+                    .await?;
+            }
+        } else if token.pixel_price.is_some() {
+            utils::transfer_nft(tx_id, &self.nft_program, &msg_source, token_id).await?;
+        }
+        token.pixel_price = pixel_price;
+        token.currency = pixel_price.and(currency);
+        self.dutch_auctions.remove(&token_id);
+
+        Ok(NFTPixelboardEvent::SaleStateChanged(token_id))
+    }
+
+    async fn start_dutch_auction(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+        dutch_auction: DutchAuction,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let (_, token) = get_mut_token(
+            &self.rectangles_by_token_ids,
+            &mut self.tokens_by_rectangles,
+            token_id,
+        )?;
+        if token.owner != msg_source {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+        if self.auctions.contains_key(&token_id) {
+            return Err(NFTPixelboardError::NFTIsOnAuction);
+        }
+        if dutch_auction.duration == 0 {
+            return Err(NFTPixelboardError::ZeroDuration);
+        }
+        check_pixel_price(dutch_auction.start_pixel_price)?;
+        if dutch_auction.floor_pixel_price > dutch_auction.start_pixel_price {
+            return Err(NFTPixelboardError::WrongFloorPixelPrice);
+        }
+
+        if token.pixel_price.is_none() {
+            utils::transfer_nft(tx_id, &self.nft_program, &exec::program_id(), token_id).await?;
+        }
+        token.pixel_price = Some(dutch_auction.start_pixel_price);
+        token.currency = None;
+        self.dutch_auctions.insert(token_id, dutch_auction);
+
+        Ok(NFTPixelboardEvent::DutchAuctionStarted(token_id))
+    }
+
+    async fn start_auction(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+        min_pixel_price: u128,
+        duration: BlockHeight,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let (_, token) = get_mut_token(
+            &self.rectangles_by_token_ids,
+            &mut self.tokens_by_rectangles,
+            token_id,
+        )?;
+        if token.owner != msg_source {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+        if token.pixel_price.is_some() {
+            return Err(NFTPixelboardError::NFTIsOnSale);
+        }
+        if self.auctions.contains_key(&token_id) {
+            return Err(NFTPixelboardError::NFTIsOnAuction);
+        }
+        if duration == 0 {
+            return Err(NFTPixelboardError::ZeroDuration);
+        }
+        check_pixel_price(min_pixel_price)?;
+
+        let ends_at = exec::block_height().saturating_add(duration);
+
+        utils::transfer_nft(tx_id, &self.nft_program, &exec::program_id(), token_id).await?;
+
+        self.auctions.insert(
+            token_id,
+            Auction {
+                seller: msg_source,
+                min_pixel_price,
+                ends_at,
+                highest_bid: None,
+            },
+        );
+
+        Ok(NFTPixelboardEvent::AuctionStarted(token_id))
+    }
+
+    async fn bid(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+        pixel_price: u128,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let rectangle = *self
+            .rectangles_by_token_ids
+            .get(&token_id)
+            .ok_or(NFTPixelboardError::NFTNotFoundById)?;
+        let auction = self
+            .auctions
+            .get(&token_id)
+            .ok_or(NFTPixelboardError::NFTIsNotOnAuction)?;
+        if auction.seller == msg_source {
+            return Err(NFTPixelboardError::OwnNFT);
+        }
+        check_bid(auction, pixel_price)?;
+        check_pixel_price(pixel_price)?;
+
+        let token_price = get_token_price(&rectangle, pixel_price);
+
+        self.take_payment(tx_id, None, &msg_source, token_price)
+            .await?;
+
+        // The auction could be settled or outbid while the bid was being paid.
+        // The bid is credited back then, as outbid ones are.
+        let auction = if let Some(auction) = self
+            .auctions
+            .get_mut(&token_id)
+            .filter(|auction| check_bid(auction, pixel_price).is_ok())
+        {
+            auction
+        } else {
+            self.credit(msg_source, None, token_price);
+
+            return Err(NFTPixelboardError::BidTooLow);
+        };
+
+        if let Some((bidder, bidder_pixel_price)) =
+            auction.highest_bid.replace((msg_source, pixel_price))
+        {
+            self.credit(
+                bidder,
+                None,
+                get_token_price(&rectangle, bidder_pixel_price),
+            );
+        }
+
+        Ok(NFTPixelboardEvent::BidPlaced(token_id))
+    }
+
+    async fn settle_auction(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let auction = *self
+            .auctions
+            .get(&token_id)
+            .ok_or(NFTPixelboardError::NFTIsNotOnAuction)?;
+        if exec::block_height() < auction.ends_at {
+            return Err(NFTPixelboardError::AuctionIsNotOver);
+        }
+        let rectangle = *self
+            .rectangles_by_token_ids
+            .get(&token_id)
+            .ok_or(NFTPixelboardError::NFTNotFoundById)?;
+
+        // Removing the auction before the transfer to prevent it from being
+        // settled twice. It's restored if the transfer fails, so the highest
+        // bid stays held & the auction can be settled again.
+        self.auctions.remove(&token_id);
+
+        let recipient = auction
+            .highest_bid
+            .map_or(auction.seller, |(winner, _)| winner);
+
+        if let Err(error) =
+            utils::transfer_nft(tx_id, &self.nft_program, &recipient, token_id).await
+        {
+            self.auctions.insert(token_id, auction);
+
+            return Err(error);
+        }
+
+        if let Some((winner, pixel_price)) = auction.highest_bid {
+            let token_price = get_token_price(&rectangle, pixel_price);
+            self.credit_resale(token_id, auction.seller, None, token_price);
+
+            if let Some(token) = self.tokens_by_rectangles.get_mut(&rectangle) {
+                token.owner = winner;
+            }
+        }
+
+        Ok(NFTPixelboardEvent::AuctionSettled(token_id))
+    }
+
+    async fn make_offer(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+        pixel_price: u128,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let (rectangle, token) = get_mut_token(
+            &self.rectangles_by_token_ids,
+            &mut self.tokens_by_rectangles,
+            token_id,
+        )?;
+        if token.owner == msg_source {
+            return Err(NFTPixelboardError::OwnNFT);
+        }
+        check_pixel_price(pixel_price)?;
+
+        let rectangle = *rectangle;
+
+        self.take_payment(
+            tx_id,
+            None,
+            &msg_source,
+            get_token_price(&rectangle, pixel_price),
+        )
+        .await?;
+
+        // A refund is credited, so a failed payment can't lose it.
+        if let Some(previous_pixel_price) = self
+            .offers
+            .entry(token_id)
+            .or_default()
+            .insert(msg_source, pixel_price)
+        {
+            self.credit(
+                msg_source,
+                None,
+                get_token_price(&rectangle, previous_pixel_price),
+            );
+        }
+
+        Ok(NFTPixelboardEvent::OfferMade(token_id))
+    }
+
+    fn cancel_offer(
+        &mut self,
+        token_id: TokenId,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let rectangle = *self
+            .rectangles_by_token_ids
+            .get(&token_id)
+            .ok_or(NFTPixelboardError::NFTNotFoundById)?;
+        let pixel_price = self
+            .remove_offer(token_id, &msg_source)
+            .ok_or(NFTPixelboardError::OfferNotFound)?;
+
+        self.credit(msg_source, None, get_token_price(&rectangle, pixel_price));
+
+        Ok(NFTPixelboardEvent::OfferCancelled(token_id))
+    }
+
+    async fn accept_offer(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+        offerer: ActorId,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let (rectangle, token) = get_mut_token(
+            &self.rectangles_by_token_ids,
+            &mut self.tokens_by_rectangles,
+            token_id,
+        )?;
+        if token.owner != msg_source {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+        if self.auctions.contains_key(&token_id) {
+            return Err(NFTPixelboardError::NFTIsOnAuction);
+        }
+        let pixel_price = if let Some(pixel_price) = self
+            .offers
+            .get(&token_id)
+            .and_then(|offers| offers.get(&offerer))
+        {
+            *pixel_price
+        } else {
+            return Err(NFTPixelboardError::OfferNotFound);
+        };
+
+        let token_price = get_token_price(rectangle, pixel_price);
+
+        utils::transfer_nft(tx_id, &self.nft_program, &offerer, token_id).await?;
+
+        token.pixel_price = None;
+        token.currency = None;
+        token.owner = offerer;
+        self.dutch_auctions.remove(&token_id);
+
+        // The offer is removed only after the NFT transfer, so fungible tokens
+        // of the offer stay refundable if the transfer fails.
+        self.remove_offer(token_id, &offerer);
+
+        self.credit_resale(token_id, msg_source, None, token_price);
+
+        Ok(NFTPixelboardEvent::OfferAccepted(token_id))
+    }
+
+    async fn transfer(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+        to: ActorId,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let (_, token) = get_mut_token(
+            &self.rectangles_by_token_ids,
+            &mut self.tokens_by_rectangles,
+            token_id,
+        )?;
+        if token.owner != msg_source {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+        if token.pixel_price.is_some() {
+            return Err(NFTPixelboardError::NFTIsOnSale);
+        }
+        if self.auctions.contains_key(&token_id) {
+            return Err(NFTPixelboardError::NFTIsOnAuction);
+        }
+        if to == ActorId::zero() {
+            return Err(NFTPixelboardError::ZeroAddress);
+        }
+
+        utils::transfer_nft(tx_id, &self.nft_program, &to, token_id).await?;
+
+        token.owner = to;
+
+        Ok(NFTPixelboardEvent::Transferred(token_id))
+    }
+
+    // Returns `true` if the owner of an NFT was changed.
+    async fn sync_owner(&mut self, token_id: TokenId) -> Result<bool, NFTPixelboardError> {
+        if !self.rectangles_by_token_ids.contains_key(&token_id) {
+            return Err(NFTPixelboardError::NFTNotFoundById);
+        }
+
+        let owner = utils::nft_owner(&self.nft_program, token_id).await?;
+
+        let (_, token) = get_mut_token(
+            &self.rectangles_by_token_ids,
+            &mut self.tokens_by_rectangles,
+            token_id,
+        )?;
+
+        // NFTs for sale & on auctions are held by the program.
+        if token.pixel_price.is_some()
+            || self.auctions.contains_key(&token_id)
+            || owner == exec::program_id()
+            || owner == token.owner
+        {
+            return Ok(false);
+        }
+
+        token.owner = owner;
+
+        Ok(true)
+    }
+
+    async fn sync_owners(
+        &mut self,
+        token_ids: Vec<TokenId>,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        if token_ids
+            .iter()
+            .any(|token_id| !self.rectangles_by_token_ids.contains_key(token_id))
+        {
+            return Err(NFTPixelboardError::NFTNotFoundById);
+        }
+
+        let mut synced = vec![];
+
+        for token_id in token_ids {
+            if self.sync_owner(token_id).await? {
+                synced.push(token_id);
+            }
+        }
+
+        Ok(NFTPixelboardEvent::OwnersSynced(synced))
+    }
+
+    async fn merge(
+        &mut self,
+        mut tx_id: TransactionId,
+        token_ids: Vec<TokenId>,
+        token_metadata: TokenMetadata,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let token_ids: BTreeSet<TokenId> = token_ids.into_iter().collect();
+        if token_ids.len() < 2 {
+            return Err(NFTPixelboardError::NotEnoughNFTs);
+        }
+
+        let rectangles = token_ids
+            .iter()
+            .map(|token_id| self.check_token_disposal(*token_id, &msg_source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let merged_rectangle = get_bounding_rectangle(&rectangles);
+
+        // Rectangles of NFTs never overlap, so they form the merged rectangle
+        // only if they cover its entire area.
+        if rectangles.iter().map(Rectangle::area).sum::<u32>() != merged_rectangle.area() {
+            return Err(NFTPixelboardError::RectanglesNotFormRectangle);
+        }
+
+        let mut royalties = token_ids
+            .iter()
+            .map(|token_id| self.royalties.get(token_id).copied());
+        let royalty = royalties.next().flatten();
+        if royalties.any(|other_royalty| other_royalty != royalty) {
+            return Err(NFTPixelboardError::RoyaltiesMismatch);
+        }
+
+        // Once the first NFT is taken, the merge stays unfinished until the
+        // merged NFT is minted, and its retry skips completed steps.
+        for token_id in &token_ids {
+            self.burn_nft(tx_id, *token_id).await?;
+            tx_id = tx_id.wrapping_add(2);
+        }
+
+        let merged_token_id = self.mint_nft_to(tx_id, &msg_source, token_metadata).await?;
+
+        for (token_id, rectangle) in token_ids.iter().zip(&rectangles) {
+            self.remove_token(*token_id, rectangle);
+        }
+        self.insert_token(merged_token_id, merged_rectangle, msg_source, royalty);
+
+        for (token_id, rectangle) in token_ids.into_iter().zip(rectangles) {
+            self.refund_offers(token_id, rectangle);
+        }
+
+        Ok(NFTPixelboardEvent::Merged(merged_token_id))
+    }
+
+    async fn split(
+        &mut self,
+        mut tx_id: TransactionId,
+        token_id: TokenId,
+        cut: SplitLine,
+        token_metadata: (TokenMetadata, TokenMetadata),
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        let rectangle = self.check_token_disposal(token_id, &msg_source)?;
+        let (first_rectangle, second_rectangle) = self.split_rectangle(rectangle, cut)?;
+        let royalty = self.royalties.get(&token_id).copied();
+
+        self.burn_nft(tx_id, token_id).await?;
+        tx_id = tx_id.wrapping_add(2);
+
+        let first_token_id = self
+            .mint_nft_to(tx_id, &msg_source, token_metadata.0)
+            .await?;
+        tx_id = tx_id.wrapping_add(2);
+
+        let second_token_id = self
+            .mint_nft_to(tx_id, &msg_source, token_metadata.1)
+            .await?;
+
+        self.remove_token(token_id, &rectangle);
+        self.insert_token(first_token_id, first_rectangle, msg_source, royalty);
+        self.insert_token(second_token_id, second_rectangle, msg_source, royalty);
+
+        self.refund_offers(token_id, rectangle);
+
+        Ok(NFTPixelboardEvent::Split(first_token_id, second_token_id))
+    }
+
+    fn split_rectangle(
+        &self,
+        rectangle: Rectangle,
+        cut: SplitLine,
+    ) -> Result<(Rectangle, Rectangle), NFTPixelboardError> {
+        let Rectangle {
+            top_left_corner: top_left,
+            bottom_right_corner: bottom_right,
+        } = rectangle;
+        let (mut first, mut second) = (rectangle, rectangle);
+
+        let (line, start, end) = match cut {
+            SplitLine::Horizontal(y) => {
+                first.bottom_right_corner.y = y;
+                second.top_left_corner.y = y;
+
+                (y, top_left.y, bottom_right.y)
+            }
+            SplitLine::Vertical(x) => {
+                first.bottom_right_corner.x = x;
+                second.top_left_corner.x = x;
+
+                (x, top_left.x, bottom_right.x)
+            }
+        };
+
+        if line % self.block_side_length != 0 || line <= start || line >= end {
+            return Err(NFTPixelboardError::WrongSplitLine);
+        }
+
+        Ok((first, second))
+    }
+
+    async fn burn(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+        restore_background: bool,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let rectangle = self.check_token_disposal(token_id, &msg::source())?;
+
+        // The NFT is removed from the pixelboard only after it's burned, and a
+        // failed burn leaves the transaction unfinished, so it can be retried.
+        self.burn_nft(tx_id, token_id).await?;
+
+        self.remove_token(token_id, &rectangle);
+        if restore_background {
+            self.restore_background(&rectangle);
+        }
+
+        self.refund_offers(token_id, rectangle);
+
+        Ok(NFTPixelboardEvent::Burned(token_id))
+    }
+
+    fn restore_background(&mut self, rectangle: &Rectangle) {
+        let canvas_width = self.resolution.width as usize;
+        let row_start = rectangle.top_left_corner.x as usize;
+        let row_end = rectangle.bottom_right_corner.x as usize;
+
+        for y in rectangle.top_left_corner.y..rectangle.bottom_right_corner.y {
+            let offset = y as usize * canvas_width;
+            let row = offset + row_start..offset + row_end;
+
+            self.painting[row.clone()].copy_from_slice(&self.background[row]);
+        }
+    }
+
+    // Checks that an NFT can be burned by its owner & returns its rectangle.
+    fn check_token_disposal(
+        &self,
+        token_id: TokenId,
+        owner: &ActorId,
+    ) -> Result<Rectangle, NFTPixelboardError> {
+        let rectangle = self
+            .rectangles_by_token_ids
+            .get(&token_id)
+            .ok_or(NFTPixelboardError::NFTNotFoundById)?;
+        let token = self
+            .tokens_by_rectangles
+            .get(rectangle)
+            .ok_or(NFTPixelboardError::NFTNotFountByRectangle)?;
+        if token.owner != *owner {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+        if token.pixel_price.is_some() {
+            return Err(NFTPixelboardError::NFTIsOnSale);
+        }
+        if self.auctions.contains_key(&token_id) {
+            return Err(NFTPixelboardError::NFTIsOnAuction);
+        }
+
+        Ok(*rectangle)
+    }
+
+    // Returns a price of a free pixel in `currency`, where `None` stands for
+    // the default one.
+    fn currency_pixel_price(&self, currency: Currency) -> Result<u128, NFTPixelboardError> {
+        match currency {
+            Some(ft_program) => self
+                .currencies
+                .get(&ft_program)
+                .copied()
+                .ok_or(NFTPixelboardError::UnknownCurrency),
+            None => Ok(self.pixel_price),
+        }
+    }
+
+    // Returns a price of minting `rectangle` in `currency`. Pricing zones are
+    // priced only in the default currency.
+    fn mint_price(
+        &self,
+        rectangle: &Rectangle,
+        currency: Currency,
+        minted_percentage: u8,
+    ) -> Result<u128, NFTPixelboardError> {
+        if currency.is_none() {
+            return Ok(mint_price(
+                rectangle,
+                self.pixel_price,
+                &self.pricing_zones,
+                self.pricing_curve,
+                minted_percentage,
+            ));
+        }
+
+        if self
+            .pricing_zones
+            .iter()
+            .any(|zone| zone.rectangle.intersection(rectangle).is_some())
+        {
+            return Err(NFTPixelboardError::WrongCurrency);
+        }
+
+        let pixel_price = self.currency_pixel_price(currency)?;
+
+        Ok(rectangle.area() as u128
+            * self.pricing_curve.map_or(pixel_price, |curve| {
+                curve.pixel_price(pixel_price, minted_percentage)
+            }))
+    }
+
+    // Returns a percentage of the canvas covered by NFTs except `excluded`
+    // reserved by `msg::source()`, so rectangles reserved by an interrupted
+    // mint are priced the same on its retry. It's only needed for a pricing
+    // curve.
+    fn minted_percentage(&self, excluded: &[Rectangle]) -> u8 {
+        if self.pricing_curve.is_none() {
+            return 0;
+        }
+
+        let msg_source = msg::source();
+        let excluded_area = excluded
+            .iter()
+            .filter(|rectangle| self.is_reserved_by(rectangle, &msg_source))
+            .map(Rectangle::area)
+            .sum();
+
+        minted_percentage(
+            self.minted_area.saturating_sub(excluded_area),
+            self.resolution.width as u32 * self.resolution.height as u32,
+        )
+    }
+
+    // Takes `amount` in `currency` from `from` to a pixelboard program.
+    async fn take_payment(
+        &mut self,
+        tx_id: TransactionId,
+        currency: Currency,
+        from: &ActorId,
+        amount: u128,
+    ) -> Result<(), NFTPixelboardError> {
+        let ft_program = match (currency, self.payment_mode) {
+            (Some(ft_program), _) => ft_program,
+            (None, PaymentMode::FungibleToken) => self.ft_program,
+            (None, PaymentMode::Value) => {
+                let deposit = self
+                    .deposits
+                    .get_mut(from)
+                    .filter(|deposit| **deposit >= amount)
+                    .ok_or(NFTPixelboardError::NotEnoughValue)?;
+                *deposit -= amount;
+
+                return Ok(());
+            }
+        };
+
+        utils::transfer_ftokens(tx_id, &ft_program, from, &exec::program_id(), amount).await
+    }
+
+    // Takes `amount` in `currency` from `from` & holds it in escrow. Returns
+    // an escrowed amount, which was taken by an interrupted attempt of this
+    // transaction if there's one, so the payment isn't taken twice.
+    async fn escrow_payment(
+        &mut self,
+        tx_id: TransactionId,
+        currency: Currency,
+        from: ActorId,
+        amount: u128,
+    ) -> Result<u128, NFTPixelboardError> {
+        if let Some(escrow) = self.escrows.get(&from) {
+            return Ok(*escrow);
+        }
+
+        self.take_payment(tx_id, currency, &from, amount).await?;
+        self.escrows.insert(from, amount);
+
+        Ok(amount)
+    }
+
+    // Sends `amount` in `currency` from a pixelboard program to `to`.
+    async fn send_payment(
+        &self,
+        tx_id: TransactionId,
+        currency: Currency,
+        to: &ActorId,
+        amount: u128,
+    ) -> Result<(), NFTPixelboardError> {
+        let ft_program = match (currency, self.payment_mode) {
+            (Some(ft_program), _) => ft_program,
+            (None, PaymentMode::FungibleToken) => self.ft_program,
+            (None, PaymentMode::Value) => return utils::send_value(to, amount),
+        };
 
-    pub rectangles_by_token_ids: BTreeMap<TokenId, Rectangle>,
-    pub tokens_by_rectangles: BTreeMap<Rectangle, TokenInfo>,
+        utils::transfer_ftokens(tx_id, &ft_program, &exec::program_id(), to, amount).await
+    }
 
-    pub ft_program: ActorId,
-    pub nft_program: ActorId,
+    // Takes 2 transaction ids starting from `tx_id`. A transaction of
+    // `msg::source()` becomes unfinished once the NFT is taken by the program.
+    async fn burn_nft(
+        &mut self,
+        tx_id: TransactionId,
+        token_id: TokenId,
+    ) -> Result<(), NFTPixelboardError> {
+        // Only the owner of an NFT can burn it, so the NFT must be transferred
+        // to the program first.
+        utils::transfer_nft(tx_id, &self.nft_program, &exec::program_id(), token_id).await?;
+        self.unfinished_txs.insert(msg::source());
 
-    pub txs: BTreeMap<ActorId, (TransactionId, NFTPixelboardAction)>,
-    pub tx_id: TransactionId,
-}
+        utils::burn_nft(tx_id.wrapping_add(1), &self.nft_program, token_id).await
+    }
 
-impl NFTPixelboard {
-    async fn mint(
+    // Takes 2 transaction ids starting from `tx_id`.
+    async fn mint_nft_to(
+        &self,
+        tx_id: TransactionId,
+        to: &ActorId,
+        token_metadata: TokenMetadata,
+    ) -> Result<TokenId, NFTPixelboardError> {
+        let token_id = utils::mint_nft(tx_id, &self.nft_program, token_metadata).await?;
+        utils::transfer_nft(tx_id.wrapping_add(1), &self.nft_program, to, token_id).await?;
+
+        Ok(token_id)
+    }
+
+    fn insert_token(
         &mut self,
-        mut tx_id: TransactionId,
+        token_id: TokenId,
         rectangle: Rectangle,
-        token_metadata: TokenMetadata,
-        painting: Vec<Color>,
-    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
-        let msg_source = msg::source();
-        let rectangle_width = rectangle.width() as usize;
-        let rectangle_height = rectangle.height() as usize;
-        let rectangle_pixel_count = get_pixel_count(rectangle_width, rectangle_height)?;
+        owner: ActorId,
+        royalty: Option<Royalty>,
+    ) {
+        self.rectangles_by_token_ids.insert(token_id, rectangle);
+        if let Some(royalty) = royalty {
+            self.royalties.insert(token_id, royalty);
+        }
+        self.occupy(
+            rectangle,
+            TokenInfo {
+                token_id: Some(token_id),
+                owner,
+                pixel_price: None,
+                currency: None,
+            },
+        );
+    }
 
-        // Payment: transfer to contract account
-        utils::transfer_ftokens(
-            tx_id,
-            &self.ft_program,
-            &msg_source,
-            &exec::program_id(),
-            rectangle_pixel_count as u128 * self.pixel_price,
-        )
-        .await?;
+    fn remove_token(&mut self, token_id: TokenId, rectangle: &Rectangle) {
+        self.rectangles_by_token_ids.remove(&token_id);
+        self.vacate(rectangle);
+        self.royalties.remove(&token_id);
+    }
 
-        if let Err(error) = self.coordinates_check(rectangle, painting.clone()) {
-            // transfer tokens back to user
-            utils::transfer_ftokens(
-                tx_id,
-                &self.ft_program,
-                &exec::program_id(),
-                &msg_source,
-                rectangle_pixel_count as u128 * self.pixel_price,
-            )
-            .await?;
-            return Err(error);
+    // Inserts a rectangle into `tokens_by_rectangles`, the occupancy index &
+    // the minted area.
+    fn occupy(&mut self, rectangle: Rectangle, token: TokenInfo) {
+        if self.tokens_by_rectangles.insert(rectangle, token).is_none() {
+            self.occupancy.occupy(&rectangle);
+            self.minted_area += rectangle.area();
         }
+    }
 
-        // Painting
-        paint(
-            self.resolution,
-            &rectangle,
-            rectangle_width,
-            rectangle_height,
-            &mut self.painting,
-            painting,
-        );
-        let token_info = self
-            .tokens_by_rectangles
-            .entry(rectangle)
-            .or_insert_with(|| TokenInfo {
-                owner: msg_source,
-                pixel_price: None,
-                token_id: None,
-            });
+    // Removes a rectangle from `tokens_by_rectangles`, the occupancy index &
+    // the minted area.
+    fn vacate(&mut self, rectangle: &Rectangle) {
+        if self.tokens_by_rectangles.remove(rectangle).is_some() {
+            self.occupancy.vacate(rectangle);
+            self.minted_area -= rectangle.area();
+        }
+    }
 
-        let token_id = utils::mint_nft(tx_id, &self.nft_program, token_metadata).await?;
-        tx_id = tx_id.wrapping_add(1);
-        utils::transfer_nft(tx_id, &self.nft_program, &msg_source, token_id).await?;
-        utils::transfer_ftokens(
-            tx_id,
-            &self.ft_program,
-            &exec::program_id(),
-            &self.owner,
-            rectangle_pixel_count as u128 * self.pixel_price,
-        )
-        .await?;
-        // Insertion and replying
-        token_info.token_id = Some(token_id);
-        self.rectangles_by_token_ids.insert(token_id, rectangle);
-        Ok(NFTPixelboardEvent::Minted(token_id))
+    fn refund_offers(&mut self, token_id: TokenId, rectangle: Rectangle) {
+        if let Some(offers) = self.offers.remove(&token_id) {
+            for (offerer, pixel_price) in offers {
+                self.credit(offerer, None, get_token_price(&rectangle, pixel_price));
+            }
+        }
     }
 
-    async fn buy(
-        &mut self,
-        mut tx_id: TransactionId,
-        token_id: TokenId,
-    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
-        let msg_source = msg::source();
-        let (rectangle, token) = get_mut_token(
-            &self.rectangles_by_token_ids,
-            &mut self.tokens_by_rectangles,
-            token_id,
-        )?;
+    fn remove_offer(&mut self, token_id: TokenId, offerer: &ActorId) -> Option<u128> {
+        let offers = self.offers.get_mut(&token_id)?;
+        let pixel_price = offers.remove(offerer);
 
-        let pixel_price = if let Some(pixel_price) = token.pixel_price {
-            pixel_price
-        } else {
-            return Err(NFTPixelboardError::NFTIsNotOnSale);
-        };
+        if offers.is_empty() {
+            self.offers.remove(&token_id);
+        }
 
-        // get_pixel_count() isn't used here because it checks an NFT area for
-        // equality to 0, but here it's always not equal 0.
-        let token_price =
-            (rectangle.width() as usize * rectangle.height() as usize) as u128 * pixel_price;
-        let resale_commission = token_price * self.commission_percentage as u128 / 100;
+        pixel_price
+    }
 
-        utils::transfer_ftokens(
-            tx_id,
-            &self.ft_program,
-            &msg_source,
-            &exec::program_id(),
-            token_price,
-        )
-        .await?;
+    fn credit(&mut self, account: ActorId, currency: Currency, amount: u128) {
+        if amount != 0 {
+            *self
+                .balances
+                .entry(account)
+                .or_default()
+                .entry(currency)
+                .or_default() += amount;
+        }
+    }
+
+    // Splits `amount` between beneficiaries by their shares. A rounding
+    // remainder goes to the first one.
+    fn credit_beneficiaries(&mut self, currency: Currency, amount: u128) {
+        if self.beneficiaries.is_empty() {
+            self.credit(self.owner, currency, amount);
+        } else {
+            let mut remainder = amount;
 
-        tx_id = tx_id.wrapping_add(1);
+            for (beneficiary, share) in self.beneficiaries.clone() {
+                let beneficiary_share = amount * share as u128 / 100;
 
-        utils::transfer_ftokens(
-            tx_id,
-            &self.ft_program,
-            &exec::program_id(),
-            &self.owner,
-            resale_commission,
-        )
-        .await?;
+                remainder -= beneficiary_share;
+                self.credit(beneficiary, currency, beneficiary_share);
+            }
 
-        tx_id = tx_id.wrapping_add(1);
+            self.credit(self.beneficiaries[0].0, currency, remainder);
+        }
+    }
 
-        utils::transfer_ftokens(
-            tx_id,
-            &self.ft_program,
-            &exec::program_id(),
-            &token.owner,
-            token_price - resale_commission,
-        )
-        .await?;
+    // Credits a resale commission, a royalty & a seller's share of `token_price`.
+    fn credit_resale(
+        &mut self,
+        token_id: TokenId,
+        seller: ActorId,
+        currency: Currency,
+        token_price: u128,
+    ) {
+        let royalty = self.royalties.get(&token_id).copied().unwrap_or_default();
+        let (commission, royalty_share, seller_share) =
+            split_resale_price(token_price, self.commission_percentage, royalty.percentage);
 
-        utils::transfer_nft(tx_id, &self.nft_program, &msg_source, token_id).await?;
+        self.credit_beneficiaries(currency, commission);
+        self.credit(royalty.minter, currency, royalty_share);
+        self.credit(seller, currency, seller_share);
+    }
 
-        token.pixel_price = None;
-        token.owner = msg_source;
+    fn debit(
+        &mut self,
+        account: &ActorId,
+        currency: Currency,
+        amount: u128,
+    ) -> Result<(), NFTPixelboardError> {
+        let balances = self
+            .balances
+            .get_mut(account)
+            .ok_or(NFTPixelboardError::InsufficientBalance)?;
+        let balance = balances
+            .get_mut(&currency)
+            .filter(|balance| **balance >= amount)
+            .ok_or(NFTPixelboardError::InsufficientBalance)?;
+        *balance -= amount;
 
-        Ok(NFTPixelboardEvent::Bought(token_id))
+        if *balance == 0 {
+            balances.remove(&currency);
+        }
+        if balances.is_empty() {
+            self.balances.remove(account);
+        }
+
+        Ok(())
     }
 
-    async fn change_sale_state(
+    async fn withdraw(
         &mut self,
         tx_id: TransactionId,
-        token_id: TokenId,
-        pixel_price: Option<u128>,
+        currency: Currency,
+        amount: u128,
     ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
         let msg_source = msg::source();
-        let (_, token) = get_mut_token(
-            &self.rectangles_by_token_ids,
-            &mut self.tokens_by_rectangles,
-            token_id,
-        )?;
-        if token.owner != msg_source {
-            return Err(NFTPixelboardError::NotOwner);
+
+        // A balance is escrowed during a withdrawal, so an interrupted one
+        // doesn't debit it twice when it's continued.
+        if !self.escrows.contains_key(&msg_source) {
+            self.debit(&msg_source, currency, amount)?;
         }
+        self.escrows.insert(msg_source, amount);
 
-        if let Some(price) = pixel_price {
-            check_pixel_price(price)?;
-            if token.pixel_price.is_none() {
-                utils::transfer_nft(tx_id, &self.nft_program, &exec::program_id(), token_id)
-                    .await?;
-            }
-        } else if token.pixel_price.is_some() {
-            utils::transfer_nft(tx_id, &self.nft_program, &msg_source, token_id).await?;
+        if let Err(error) = self
+            .send_payment(tx_id, currency, &msg_source, amount)
+            .await
+        {
+            self.credit(msg_source, currency, amount);
+            self.escrows.remove(&msg_source);
+
+            return Err(error);
         }
-        token.pixel_price = pixel_price;
+        self.escrows.remove(&msg_source);
 
-        Ok(NFTPixelboardEvent::SaleStateChanged(token_id))
+        Ok(NFTPixelboardEvent::Withdrawn(amount))
     }
 
     fn paint(
@@ -284,6 +1690,191 @@ impl NFTPixelboard {
         Ok(NFTPixelboardEvent::Painted(token_id))
     }
 
+    fn update_config(
+        &mut self,
+        update: ConfigUpdate,
+    ) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        if msg::source() != self.owner {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+
+        match update {
+            ConfigUpdate::PixelPrice(pixel_price) => {
+                check_pixel_price(pixel_price)?;
+                self.pixel_price = pixel_price;
+
+                Ok(NFTPixelboardEvent::PixelPriceUpdated(pixel_price))
+            }
+            ConfigUpdate::CommissionPercentage(commission_percentage) => {
+                check_commission_percentage(commission_percentage)?;
+                self.commission_percentage = commission_percentage;
+
+                Ok(NFTPixelboardEvent::CommissionPercentageUpdated(
+                    commission_percentage,
+                ))
+            }
+            ConfigUpdate::FTProgram(ft_program) => {
+                check_address(&ft_program)?;
+
+                // Escrowed tokens can be returned only from the FT program
+                // they were received from.
+                if !self.offers.is_empty()
+                    || !self.escrows.is_empty()
+                    || self
+                        .balances
+                        .values()
+                        .any(|balances| balances.contains_key(&None))
+                    || self
+                        .auctions
+                        .values()
+                        .any(|auction| auction.highest_bid.is_some())
+                {
+                    return Err(NFTPixelboardError::FundsInEscrow);
+                }
+
+                self.ft_program = ft_program;
+
+                Ok(NFTPixelboardEvent::FTProgramUpdated(ft_program))
+            }
+            ConfigUpdate::AddCurrency {
+                ft_program,
+                pixel_price,
+            } => {
+                check_address(&ft_program)?;
+                check_pixel_price(pixel_price)?;
+                self.currencies.insert(ft_program, pixel_price);
+
+                Ok(NFTPixelboardEvent::CurrencyAdded(ft_program))
+            }
+            ConfigUpdate::RemoveCurrency(ft_program) => {
+                self.currencies
+                    .remove(&ft_program)
+                    .ok_or(NFTPixelboardError::UnknownCurrency)?;
+
+                Ok(NFTPixelboardEvent::CurrencyRemoved(ft_program))
+            }
+            ConfigUpdate::Beneficiaries(beneficiaries) => {
+                check_beneficiaries(&beneficiaries)?;
+                self.beneficiaries = beneficiaries;
+
+                Ok(NFTPixelboardEvent::BeneficiariesUpdated)
+            }
+            ConfigUpdate::PricingZones(pricing_zones) => {
+                check_pricing_zones(&pricing_zones, self.resolution)?;
+                self.pricing_zones = pricing_zones;
+
+                Ok(NFTPixelboardEvent::PricingZonesUpdated)
+            }
+        }
+    }
+
+    fn propose_owner(&mut self, owner: ActorId) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        if msg::source() != self.owner {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+        check_address(&owner)?;
+
+        self.pending_owner = Some(owner);
+
+        Ok(NFTPixelboardEvent::OwnerProposed(owner))
+    }
+
+    fn accept_ownership(&mut self) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        let msg_source = msg::source();
+        if self.pending_owner != Some(msg_source) {
+            return Err(NFTPixelboardError::NotProposedOwner);
+        }
+
+        self.owner = msg_source;
+        self.pending_owner = None;
+
+        Ok(NFTPixelboardEvent::OwnershipAccepted(msg_source))
+    }
+
+    fn pause(&mut self, kind: ActionKind) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        if msg::source() != self.owner {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+
+        self.paused.insert(kind);
+
+        Ok(NFTPixelboardEvent::Paused(kind))
+    }
+
+    fn unpause(&mut self, kind: ActionKind) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        if msg::source() != self.owner {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+
+        self.paused.remove(&kind);
+
+        Ok(NFTPixelboardEvent::Unpaused(kind))
+    }
+
+    async fn cancel_tx(&mut self, user: ActorId) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+        if msg::source() != self.owner {
+            return Err(NFTPixelboardError::NotOwner);
+        }
+        let currency = match self.txs.get(&user) {
+            Some((_, NFTPixelboardAction::Mint { currency, .. }))
+            | Some((_, NFTPixelboardAction::Buy { currency, .. }))
+            | Some((_, NFTPixelboardAction::BuyBatch { currency, .. }))
+            | Some((_, NFTPixelboardAction::Withdraw { currency, .. })) => *currency,
+            Some(_) => None,
+            None => return Err(NFTPixelboardError::TxNotFound),
+        };
+        self.check_tx_interrupted(&user)?;
+        // NFTs taken from `user` can't be returned, so such a transaction can
+        // only be completed.
+        if self.unfinished_txs.contains(&user) {
+            return Err(NFTPixelboardError::TxNotCancellable);
+        }
+
+        // An interrupted mint can leave reserved rectangles.
+        let rectangles = match self.txs.get(&user) {
+            Some((_, NFTPixelboardAction::Mint { rectangle, .. })) => vec![*rectangle],
+            Some((_, NFTPixelboardAction::MintBatch(tokens))) => {
+                tokens.iter().map(|(rectangle, ..)| *rectangle).collect()
+            }
+            _ => vec![],
+        };
+        for rectangle in rectangles {
+            if self.is_reserved_by(&rectangle, &user) {
+                self.vacate(&rectangle);
+            }
+        }
+
+        if let Some(escrow) = self.escrows.get(&user).copied() {
+            // An escrowed balance of an interrupted withdrawal is credited
+            // back instead.
+            if let Some((_, NFTPixelboardAction::Withdraw { .. })) = self.txs.get(&user) {
+                self.credit(user, currency, escrow);
+            } else {
+                let tx_id = self.tx_id;
+                self.tx_id = self.tx_id.wrapping_add(1);
+
+                self.send_payment(tx_id, currency, &user, escrow).await?;
+            }
+            self.escrows.remove(&user);
+        }
+
+        self.txs_in_progress.remove(&user);
+        self.txs.remove(&user);
+
+        Ok(NFTPixelboardEvent::TxCancelled(user))
+    }
+
+    // A transaction that's still in progress may be waiting for a reply, so it
+    // can be continued or cancelled only after `TX_TIMEOUT`.
+    fn check_tx_interrupted(&self, user: &ActorId) -> Result<(), NFTPixelboardError> {
+        match self.txs_in_progress.get(user) {
+            Some(started_at) if exec::block_height() < started_at.saturating_add(TX_TIMEOUT) => {
+                Err(NFTPixelboardError::TxInProgress)
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn coordinates_check(
         &self,
         rectangle: Rectangle,
@@ -309,12 +1900,7 @@ impl NFTPixelboard {
             return Err(NFTPixelboardError::CoordinatesOutOfCanvas);
         }
 
-        if self.tokens_by_rectangles.keys().any(|existing_rectangle| {
-            existing_rectangle.top_left_corner.x < rectangle.bottom_right_corner.x
-                && existing_rectangle.bottom_right_corner.x > rectangle.top_left_corner.x
-                && existing_rectangle.top_left_corner.y < rectangle.bottom_right_corner.y
-                && existing_rectangle.bottom_right_corner.y > rectangle.top_left_corner.y
-        }) {
+        if self.occupancy.collides(&rectangle) {
             return Err(NFTPixelboardError::CoordinatesCollision);
         }
 
@@ -349,14 +1935,17 @@ fn process_init() -> Result<(), NFTPixelboardError> {
         painting,
         resolution,
         commission_percentage,
+        royalty_percentage,
+        pricing_curve,
         pixel_price,
+        payment_mode,
     } = msg::load().expect("Unable to decode `InitNFTPixelboard`");
 
     if owner == ActorId::zero() {
         return Err(NFTPixelboardError::ZeroAddress);
     }
 
-    if ft_program == ActorId::zero() {
+    if payment_mode == PaymentMode::FungibleToken && ft_program == ActorId::zero() {
         return Err(NFTPixelboardError::ZeroAddress);
     }
 
@@ -381,22 +1970,24 @@ fn process_init() -> Result<(), NFTPixelboardError> {
         return Err(NFTPixelboardError::WrongResolution);
     }
 
-    if commission_percentage > 100 {
-        return Err(NFTPixelboardError::WrongCommissionPercentage);
-    }
+    check_commission_percentage(commission_percentage)?;
+    check_royalty_percentage(royalty_percentage)?;
+    check_pixel_price(pixel_price)?;
 
-    if pixel_price > MAX_PIXEL_PRICE {
-        return Err(NFTPixelboardError::PixelPriceExceeded);
-    }
     let program = NFTPixelboard {
         owner,
         ft_program,
         nft_program,
+        payment_mode,
         block_side_length,
+        background: painting.clone(),
         painting,
         pixel_price,
         commission_percentage,
+        royalty_percentage,
+        pricing_curve,
         resolution,
+        occupancy: Occupancy::new(resolution, block_side_length),
         ..Default::default()
     };
     unsafe {
@@ -410,54 +2001,193 @@ async fn main() {
     let program = unsafe { PROGRAM.get_or_insert(Default::default()) };
     let msg_source = msg::source();
 
-    let _reply: Result<NFTPixelboardEvent, NFTPixelboardError> =
-        Err(NFTPixelboardError::PreviousTxMustBeCompleted);
+    if program.payment_mode == PaymentMode::Value {
+        *program.deposits.entry(msg_source).or_default() += msg::value();
+    }
+
+    let result = process(program, action).await;
+
+    // An unspent value is returned with a reply.
+    let change = program.deposits.remove(&msg_source).unwrap_or_default();
+    msg::reply(result, change)
+        .expect("Failed to encode or reply with `Result<NFTPixelboardEvent, NFTPixelboardError>`");
+}
+
+async fn process(
+    program: &mut NFTPixelboard,
+    action: NFTPixelboardAction,
+) -> Result<NFTPixelboardEvent, NFTPixelboardError> {
+    let msg_source = msg::source();
+
+    let action = match action {
+        NFTPixelboardAction::ContinueTx => program
+            .txs
+            .get(&msg_source)
+            .map(|(_, action)| action.clone())
+            .ok_or(NFTPixelboardError::TxNotFound)?,
+        NFTPixelboardAction::CancelTx(user) => return program.cancel_tx(user).await,
+        // Administration doesn't call other programs, so it bypasses
+        // transactions & isn't blocked by a pending transaction of the owner.
+        NFTPixelboardAction::UpdateConfig(update) => return program.update_config(update),
+        NFTPixelboardAction::ProposeOwner(owner) => return program.propose_owner(owner),
+        NFTPixelboardAction::Pause(kind) => return program.pause(kind),
+        NFTPixelboardAction::Unpause(kind) => return program.unpause(kind),
+        action => action,
+    };
+
+    if matches!(action_kind(&action), Some(kind) if program.paused.contains(&kind)) {
+        return Err(NFTPixelboardError::Paused);
+    }
+
     let tx_id = if let Some((tx_id, pend_action)) = program.txs.get(&msg_source) {
         if action != *pend_action {
-            reply(_reply).expect(
-                "Failed to encode or reply with `Result<NFTPixelboardEvent, NFTPixelboardError>`",
-            );
-            return;
+            return Err(NFTPixelboardError::PreviousTxMustBeCompleted);
         }
-        *tx_id
+        let tx_id = *tx_id;
+        program.check_tx_interrupted(&msg_source)?;
+        tx_id
     } else {
         let tx_id = program.tx_id;
-        program.tx_id = program.tx_id.wrapping_add(MIN_STEP_FOR_TX);
+        program.tx_id = program.tx_id.wrapping_add(tx_step(&action));
         program.txs.insert(msg_source, (tx_id, action.clone()));
         tx_id
     };
+    program
+        .txs_in_progress
+        .insert(msg_source, exec::block_height());
 
-    let result = match action.clone() {
+    let result = match action {
         NFTPixelboardAction::Mint {
             rectangle,
             token_metadata,
             painting,
+            currency,
+            royalty_percentage,
         } => {
-            let reply = program
-                .mint(tx_id, rectangle, token_metadata, painting)
-                .await;
-            program.txs.remove(&msg_source);
-            reply
+            program
+                .mint(
+                    tx_id,
+                    rectangle,
+                    token_metadata,
+                    painting,
+                    currency,
+                    royalty_percentage,
+                )
+                .await
         }
-        NFTPixelboardAction::Buy(token_id) => {
-            let reply = program.buy(tx_id, token_id).await;
-            program.txs.remove(&msg_source);
-            reply
+        NFTPixelboardAction::Buy { token_id, currency } => {
+            program.buy(tx_id, token_id, currency).await
         }
         NFTPixelboardAction::ChangeSaleState {
             token_id,
             pixel_price,
+            currency,
         } => {
-            let reply = program
-                .change_sale_state(tx_id, token_id, pixel_price)
-                .await;
-            program.txs.remove(&msg_source);
-            reply
+            program
+                .change_sale_state(tx_id, token_id, pixel_price, currency)
+                .await
         }
         NFTPixelboardAction::Paint { token_id, painting } => program.paint(token_id, painting),
+        NFTPixelboardAction::StartAuction {
+            token_id,
+            min_pixel_price,
+            duration,
+        } => {
+            program
+                .start_auction(tx_id, token_id, min_pixel_price, duration)
+                .await
+        }
+        NFTPixelboardAction::Bid {
+            token_id,
+            pixel_price,
+        } => program.bid(tx_id, token_id, pixel_price).await,
+        NFTPixelboardAction::SettleAuction(token_id) => {
+            program.settle_auction(tx_id, token_id).await
+        }
+        NFTPixelboardAction::MakeOffer {
+            token_id,
+            pixel_price,
+        } => program.make_offer(tx_id, token_id, pixel_price).await,
+        NFTPixelboardAction::CancelOffer(token_id) => program.cancel_offer(token_id),
+        NFTPixelboardAction::AcceptOffer { token_id, offerer } => {
+            program.accept_offer(tx_id, token_id, offerer).await
+        }
+        NFTPixelboardAction::Transfer { token_id, to } => {
+            program.transfer(tx_id, token_id, to).await
+        }
+        NFTPixelboardAction::SyncOwner(token_id) => program
+            .sync_owner(token_id)
+            .await
+            .map(|_| NFTPixelboardEvent::OwnerSynced(token_id)),
+        NFTPixelboardAction::SyncOwners(token_ids) => program.sync_owners(token_ids).await,
+        NFTPixelboardAction::Merge {
+            token_ids,
+            token_metadata,
+        } => program.merge(tx_id, token_ids, token_metadata).await,
+        NFTPixelboardAction::Split {
+            token_id,
+            cut,
+            token_metadata,
+        } => program.split(tx_id, token_id, cut, token_metadata).await,
+        NFTPixelboardAction::Burn {
+            token_id,
+            restore_background,
+        } => program.burn(tx_id, token_id, restore_background).await,
+        NFTPixelboardAction::AcceptOwnership => program.accept_ownership(),
+        NFTPixelboardAction::StartDutchAuction {
+            token_id,
+            start_pixel_price,
+            floor_pixel_price,
+            duration,
+        } => {
+            program
+                .start_dutch_auction(
+                    tx_id,
+                    token_id,
+                    DutchAuction {
+                        start_pixel_price,
+                        floor_pixel_price,
+                        started_at: exec::block_height(),
+                        duration,
+                    },
+                )
+                .await
+        }
+        NFTPixelboardAction::Withdraw { currency, amount } => {
+            program.withdraw(tx_id, currency, amount).await
+        }
+        NFTPixelboardAction::MintBatch(tokens) => program.mint_batch(tx_id, tokens).await,
+        NFTPixelboardAction::BuyBatch {
+            token_ids,
+            max_total_price,
+            currency,
+        } => {
+            program
+                .buy_batch(tx_id, token_ids, max_total_price, currency)
+                .await
+        }
+        NFTPixelboardAction::ContinueTx
+        | NFTPixelboardAction::CancelTx(_)
+        | NFTPixelboardAction::UpdateConfig(_)
+        | NFTPixelboardAction::ProposeOwner(_)
+        | NFTPixelboardAction::Pause(_)
+        | NFTPixelboardAction::Unpause(_) => {
+            unreachable!("Transaction management & administration actions are processed above")
+        }
     };
-    reply(result)
-        .expect("Failed to encode or reply with `Result<NFTPixelboardEvent, NFTPixelboardError>`");
+
+    program.txs_in_progress.remove(&msg_source);
+    if result.is_ok() {
+        program.unfinished_txs.remove(&msg_source);
+    }
+
+    // A transaction that holds fungible tokens of `msg_source` or can't be
+    // rolled back stays pending, so it can be continued or cancelled later.
+    if !program.escrows.contains_key(&msg_source) && !program.unfinished_txs.contains(&msg_source) {
+        program.txs.remove(&msg_source);
+    }
+
+    result
 }
 
 fn reply(payload: impl Encode) -> GstdResult<MessageId> {
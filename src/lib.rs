@@ -3,6 +3,8 @@
 #[cfg(not(feature = "binary-vendor"))]
 mod contract;
 #[cfg(not(feature = "binary-vendor"))]
+mod occupancy;
+#[cfg(not(feature = "binary-vendor"))]
 mod utils;
 
 // See `Cargo.toml` for the description of the "binary-vendor" feature.
@@ -0,0 +1,87 @@
+use gstd::prelude::*;
+use nft_pixelboard_io::*;
+
+/// A block-granular index of [`Rectangle`]s occupied by NFTs & pending mints
+/// on a canvas.
+///
+/// Each block of a canvas stores a compact index of a rectangle covering it, so
+/// a collision check costs an area of a rectangle in blocks instead of a number
+/// of NFTs.
+#[derive(Default)]
+pub struct Occupancy {
+    block_side_length: BlockSideLength,
+    // A canvas width in blocks.
+    width: BlockSideLength,
+    // Blocks of a canvas row by row. 0 stands for a free block, otherwise it's
+    // an index of a covering rectangle in `rectangles` plus 1.
+    blocks: Vec<u32>,
+    // Occupying rectangles by indexes. Indexes of vacated rectangles are
+    // reused.
+    rectangles: Vec<Option<Rectangle>>,
+    free_indexes: Vec<u32>,
+}
+
+impl Occupancy {
+    pub fn new(resolution: Resolution, block_side_length: BlockSideLength) -> Self {
+        let width = resolution.width / block_side_length;
+        let height = resolution.height / block_side_length;
+
+        Self {
+            block_side_length,
+            width,
+            blocks: vec![0; width as usize * height as usize],
+            ..Default::default()
+        }
+    }
+
+    // `rectangle` must observe a block layout & lie inside a canvas.
+    fn block_indexes(&self, rectangle: &Rectangle) -> impl Iterator<Item = usize> {
+        let block_side_length = self.block_side_length as usize;
+        let width = self.width as usize;
+        let rows = rectangle.top_left_corner.y as usize / block_side_length
+            ..rectangle.bottom_right_corner.y as usize / block_side_length;
+        let columns = rectangle.top_left_corner.x as usize / block_side_length
+            ..rectangle.bottom_right_corner.x as usize / block_side_length;
+
+        rows.flat_map(move |row| columns.clone().map(move |column| row * width + column))
+    }
+
+    /// Marks blocks of `rectangle` as occupied by it.
+    pub fn occupy(&mut self, rectangle: &Rectangle) {
+        let index = if let Some(index) = self.free_indexes.pop() {
+            self.rectangles[index as usize] = Some(*rectangle);
+            index
+        } else {
+            self.rectangles.push(Some(*rectangle));
+            self.rectangles.len() as u32 - 1
+        };
+
+        for block in self.block_indexes(rectangle) {
+            self.blocks[block] = index + 1;
+        }
+    }
+
+    /// Marks blocks of `rectangle` as free if they're occupied by it.
+    pub fn vacate(&mut self, rectangle: &Rectangle) {
+        let index = match self
+            .block_indexes(rectangle)
+            .next()
+            .and_then(|block| self.blocks[block].checked_sub(1))
+        {
+            Some(index) if self.rectangles[index as usize] == Some(*rectangle) => index,
+            _ => return,
+        };
+
+        for block in self.block_indexes(rectangle) {
+            self.blocks[block] = 0;
+        }
+        self.rectangles[index as usize] = None;
+        self.free_indexes.push(index);
+    }
+
+    /// Checks whether any block of `rectangle` is occupied.
+    pub fn collides(&self, rectangle: &Rectangle) -> bool {
+        self.block_indexes(rectangle)
+            .any(|block| self.blocks[block] != 0)
+    }
+}
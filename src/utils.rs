@@ -1,7 +1,7 @@
 use crate::contract::NFTPixelboard;
 use ft_main_io::{FTokenAction, FTokenEvent, LogicAction};
 use gear_lib::non_fungible_token::token::{TokenId, TokenMetadata};
-use gstd::{msg, prelude::*, ActorId};
+use gstd::{exec, msg, prelude::*, ActorId};
 use nft_io::{NFTAction, NFTEvent};
 use nft_pixelboard_io::*;
 
@@ -33,6 +33,16 @@ pub async fn transfer_ftokens(
     }
 }
 
+pub fn send_value(to: &ActorId, amount: u128) -> Result<(), NFTPixelboardError> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    msg::send_bytes(*to, [], amount)
+        .map(|_| ())
+        .map_err(|_| NFTPixelboardError::ValueTransferFailed)
+}
+
 pub async fn transfer_nft(
     transaction_id: TransactionId,
     nft_program: &ActorId,
@@ -79,15 +89,54 @@ pub async fn mint_nft(
     }
 }
 
+pub async fn burn_nft(
+    transaction_id: TransactionId,
+    nft_program: &ActorId,
+    token_id: TokenId,
+) -> Result<(), NFTPixelboardError> {
+    let reply = msg::send_for_reply_as::<_, NFTEvent>(
+        *nft_program,
+        NFTAction::Burn {
+            transaction_id,
+            token_id,
+        },
+        0,
+        0,
+    )
+    .expect("Error during sending `NFTAction::Burn` to an NFT program")
+    .await;
+    match reply {
+        Ok(NFTEvent::Transfer(_)) => Ok(()),
+        _ => Err(NFTPixelboardError::NFTBurnFailed),
+    }
+}
+
+pub async fn nft_owner(
+    nft_program: &ActorId,
+    token_id: TokenId,
+) -> Result<ActorId, NFTPixelboardError> {
+    let reply =
+        msg::send_for_reply_as::<_, NFTEvent>(*nft_program, NFTAction::Owner { token_id }, 0, 0)
+            .expect("Error during sending `NFTAction::Owner` to an NFT program")
+            .await;
+    match reply {
+        Ok(NFTEvent::Owner { owner, .. }) => Ok(owner),
+        _ => Err(NFTPixelboardError::NFTOwnerRequestFailed),
+    }
+}
+
 impl From<&NFTPixelboard> for NFTPixelboardState {
     fn from(state: &NFTPixelboard) -> NFTPixelboardState {
         NFTPixelboardState {
             owner: state.owner,
+            pending_owner: state.pending_owner,
             block_side_length: state.block_side_length,
             pixel_price: state.pixel_price,
             resolution: state.resolution,
             commission_percentage: state.commission_percentage,
+            royalty_percentage: state.royalty_percentage,
             painting: state.painting.clone(),
+            background: state.background.clone(),
             rectangles_by_token_ids: state
                 .rectangles_by_token_ids
                 .iter()
@@ -98,14 +147,77 @@ impl From<&NFTPixelboard> for NFTPixelboardState {
                 .iter()
                 .map(|(key, value)| (*key, *value))
                 .collect(),
+            minted_area: state.minted_area,
             ft_program: state.ft_program,
             nft_program: state.nft_program,
+            payment_mode: state.payment_mode,
+            currencies: state
+                .currencies
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
             txs: state
                 .txs
                 .iter()
                 .map(|(key, value)| (*key, value.clone()))
                 .collect(),
             tx_id: state.tx_id,
+            escrows: state
+                .escrows
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
+            txs_in_progress: state
+                .txs_in_progress
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
+            balances: state
+                .balances
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        *key,
+                        value
+                            .iter()
+                            .map(|(currency, balance)| (*currency, *balance))
+                            .collect(),
+                    )
+                })
+                .collect(),
+            royalties: state
+                .royalties
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
+            beneficiaries: state.beneficiaries.clone(),
+            pricing_zones: state.pricing_zones.clone(),
+            pricing_curve: state.pricing_curve,
+            auctions: state
+                .auctions
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
+            dutch_auctions: state
+                .dutch_auctions
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
+            offers: state
+                .offers
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        *key,
+                        value
+                            .iter()
+                            .map(|(offerer, pixel_price)| (*offerer, *pixel_price))
+                            .collect(),
+                    )
+                })
+                .collect(),
+            paused: state.paused.iter().copied().collect(),
+            block_height: exec::block_height(),
         }
     }
 }
@@ -19,6 +19,56 @@ pub mod metafns {
         state.pixel_price
     }
 
+    pub fn currencies(state: State) -> Vec<(ActorId, u128)> {
+        state.currencies
+    }
+
+    pub fn beneficiaries(state: State) -> Vec<(ActorId, u8)> {
+        state.beneficiaries
+    }
+
+    pub fn pixel_price_quote(state: State) -> u128 {
+        let minted_percentage = state_minted_percentage(&state);
+
+        state.pricing_curve.map_or(state.pixel_price, |curve| {
+            curve.pixel_price(state.pixel_price, minted_percentage)
+        })
+    }
+
+    pub fn mint_price_quote(state: State, rectangle: Rectangle) -> u128 {
+        let minted_percentage = state_minted_percentage(&state);
+
+        mint_price(
+            &rectangle,
+            state.pixel_price,
+            &state.pricing_zones,
+            state.pricing_curve,
+            minted_percentage,
+        )
+    }
+
+    pub fn royalty(state: State, token_id: TokenId) -> Option<Royalty> {
+        state
+            .royalties
+            .into_iter()
+            .find(|(key, _)| *key == token_id)
+            .map(|(_, royalty)| royalty)
+    }
+
+    pub fn balance(state: State, account: ActorId, currency: Currency) -> u128 {
+        state
+            .balances
+            .into_iter()
+            .find(|(key, _)| *key == account)
+            .and_then(|(_, balances)| {
+                balances
+                    .into_iter()
+                    .find(|(key, _)| *key == currency)
+                    .map(|(_, balance)| balance)
+            })
+            .unwrap_or_default()
+    }
+
     pub fn block_side_length(state: State) -> BlockSideLength {
         state.block_side_length
     }
@@ -27,20 +77,12 @@ pub mod metafns {
         state.commission_percentage
     }
 
-    pub fn pixel_info(state: State, coordinates: Coordinates) -> Token {
-        let mut token = Default::default();
-
-        if coordinates.x < state.resolution.width && coordinates.y < state.resolution.height {
-            for (rectangle, token_info) in state.tokens_by_rectangles.iter() {
-                if coordinates.x < rectangle.bottom_right_corner.x
-                    && coordinates.y < rectangle.bottom_right_corner.y
-                {
-                    token = Token(*rectangle, *token_info)
-                }
-            }
-        }
-
-        token
+    pub fn pixel_info(state: State, coordinates: Coordinates) -> Option<Token> {
+        state
+            .tokens_by_rectangles
+            .into_iter()
+            .find(|(rectangle, _)| rectangle.contains(coordinates))
+            .map(|(rectangle, token_info)| Token(rectangle, token_info))
     }
 
     pub fn token_info(state: State, token_id: TokenId) -> Token {
@@ -69,4 +111,106 @@ pub mod metafns {
     pub fn nft_program(state: State) -> ActorId {
         state.nft_program
     }
+
+    pub fn auctions(state: State) -> Vec<(TokenId, Auction)> {
+        state.auctions
+    }
+
+    pub fn auction(state: State, token_id: TokenId) -> Option<Auction> {
+        state
+            .auctions
+            .into_iter()
+            .find_map(|(id, auction)| (id == token_id).then_some(auction))
+    }
+
+    pub fn dutch_auctions(state: State) -> Vec<(TokenId, DutchAuction)> {
+        state.dutch_auctions
+    }
+
+    pub fn offers(state: State, token_id: TokenId) -> Vec<(ActorId, u128)> {
+        state
+            .offers
+            .into_iter()
+            .find_map(|(id, offers)| (id == token_id).then_some(offers))
+            .unwrap_or_default()
+    }
+
+    pub fn paused(state: State) -> Vec<ActionKind> {
+        state.paused
+    }
+
+    pub fn pending_txs(state: State) -> Vec<(ActorId, PendingTx)> {
+        let escrows = state.escrows;
+        let txs_in_progress = state.txs_in_progress;
+
+        state
+            .txs
+            .into_iter()
+            .map(|(user, (tx_id, action))| {
+                let escrow = escrows
+                    .iter()
+                    .find_map(|(x, amount)| (x == &user).then_some(*amount))
+                    .unwrap_or_default();
+                let started_at = txs_in_progress
+                    .iter()
+                    .find_map(|(x, started_at)| (x == &user).then_some(*started_at));
+
+                (
+                    user,
+                    PendingTx {
+                        tx_id,
+                        action,
+                        escrow,
+                        started_at,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    pub fn pending_tx(state: State, user: ActorId) -> Option<PendingTx> {
+        let (_, (tx_id, action)) = state.txs.into_iter().find(|(x, _)| x == &user)?;
+        let escrow = state
+            .escrows
+            .into_iter()
+            .find_map(|(x, amount)| (x == user).then_some(amount))
+            .unwrap_or_default();
+        let started_at = state
+            .txs_in_progress
+            .into_iter()
+            .find_map(|(x, started_at)| (x == user).then_some(started_at));
+
+        Some(PendingTx {
+            tx_id,
+            action,
+            escrow,
+            started_at,
+        })
+    }
+
+    pub fn current_pixel_price(state: State, token_id: TokenId) -> Option<u128> {
+        if let Some((_, dutch_auction)) =
+            state.dutch_auctions.iter().find(|(id, _)| id == &token_id)
+        {
+            return Some(dutch_auction.pixel_price(state.block_height));
+        }
+
+        let rectangle = state
+            .rectangles_by_token_ids
+            .iter()
+            .find_map(|(id, rectangle)| (id == &token_id).then_some(rectangle))?;
+
+        state
+            .tokens_by_rectangles
+            .iter()
+            .find_map(|(x, token_info)| (x == rectangle).then_some(token_info.pixel_price))
+            .flatten()
+    }
+}
+
+fn state_minted_percentage(state: &metafns::State) -> u8 {
+    minted_percentage(
+        state.minted_area,
+        state.resolution.width as u32 * state.resolution.height as u32,
+    )
 }
@@ -0,0 +1,151 @@
+pub mod utils;
+use utils::{prelude::*, MockFungibleToken, MockNonFungibleToken, FOREIGN_USER, OWNER, USER};
+
+#[test]
+fn auction() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 75);
+    ft_program.mint(FOREIGN_USER, 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 13,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program =
+        NFTPixelboard::initialize_custom(&system, pixelboard_config.clone()).succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+    pixelboard_program
+        .start_auction(USER[0], 0, 2, 10)
+        .succeed(0);
+
+    pixelboard_program.bid(USER[1], 0, 2).succeed(0);
+    assert_eq!(ft_program.balance(USER[1]), 25);
+
+    // Outbidding USER[1] should credit its fungible tokens back.
+    pixelboard_program.bid(FOREIGN_USER, 0, 3).succeed(0);
+    pixelboard_program.withdraw(USER[1], 50).succeed(50);
+    assert_eq!(ft_program.balance(USER[1]), 75);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 25);
+
+    system.spend_blocks(10);
+
+    pixelboard_program.settle_auction(USER[1], 0).succeed(0);
+
+    let token_price = 25 * 3;
+    let commission = token_price * pixelboard_config.commission_percentage as u128 / 100;
+    pixelboard_program
+        .withdraw(OWNER, 25 + commission)
+        .succeed(25 + commission);
+    pixelboard_program
+        .withdraw(USER[0], token_price - commission)
+        .succeed(token_price - commission);
+    assert_eq!(ft_program.balance(OWNER), 25 + commission);
+    assert_eq!(ft_program.balance(USER[0]), token_price - commission);
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 0);
+    assert_eq!(nft_program.owner(0), Some(FOREIGN_USER.into()));
+
+    // Only the new owner can put the NFT up for sale.
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(1))
+        .failed(NFTPixelboardError::NotOwner);
+    pixelboard_program
+        .change_sale_state(FOREIGN_USER, 0, Some(1))
+        .succeed(0);
+}
+
+#[test]
+fn auction_failures() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 50);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 13,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+
+    // Should fail because the NFT isn't on an auction.
+    pixelboard_program
+        .bid(USER[1], 0, 1)
+        .failed(NFTPixelboardError::NFTIsNotOnAuction);
+    // Should fail because USER[1] isn't the owner of the NFT.
+    pixelboard_program
+        .start_auction(USER[1], 0, 1, 10)
+        .failed(NFTPixelboardError::NotOwner);
+    // Should fail because `duration` must be more than 0.
+    pixelboard_program
+        .start_auction(USER[0], 0, 1, 0)
+        .failed(NFTPixelboardError::ZeroDuration);
+    // Should fail because `min_pixel_price` mustn't be more than `MAX_PIXEL_PRICE`.
+    pixelboard_program
+        .start_auction(USER[0], 0, MAX_PIXEL_PRICE + 1, 10)
+        .failed(NFTPixelboardError::PixelPriceExceeded);
+
+    pixelboard_program
+        .start_auction(USER[0], 0, 1, 10)
+        .succeed(0);
+
+    // Should fail because the NFT is already on an auction.
+    pixelboard_program
+        .start_auction(USER[0], 0, 1, 10)
+        .failed(NFTPixelboardError::NFTIsOnAuction);
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(1))
+        .failed(NFTPixelboardError::NFTIsOnAuction);
+    // Should fail because the auction isn't over.
+    pixelboard_program
+        .settle_auction(USER[0], 0)
+        .failed(NFTPixelboardError::AuctionIsNotOver);
+
+    // Should fail because USER[0] is the seller of the NFT.
+    pixelboard_program
+        .bid(USER[0], 0, 1)
+        .failed(NFTPixelboardError::OwnNFT);
+
+    pixelboard_program.bid(USER[1], 0, 1).succeed(0);
+
+    // Should fail because a bid must be more than the highest one.
+    pixelboard_program
+        .bid(USER[1], 0, 1)
+        .failed(NFTPixelboardError::BidTooLow);
+
+    system.spend_blocks(10);
+
+    // Should fail because the auction is over.
+    pixelboard_program
+        .bid(USER[1], 0, 2)
+        .failed(NFTPixelboardError::AuctionIsOver);
+
+    nft_program.fail_transfer(true);
+    // Should fail because the NFT program fails to transfer the NFT.
+    pixelboard_program
+        .settle_auction(USER[0], 0)
+        .failed(NFTPixelboardError::NFTTransferFailed);
+    nft_program.fail_transfer(false);
+
+    // The auction should stay unsettled, so the seller isn't paid yet.
+    pixelboard_program
+        .withdraw(USER[0], 1)
+        .failed(NFTPixelboardError::InsufficientBalance);
+
+    pixelboard_program.settle_auction(USER[0], 0).succeed(0);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+    pixelboard_program.withdraw(USER[0], 25 - 3).succeed(25 - 3);
+}
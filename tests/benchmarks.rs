@@ -0,0 +1,51 @@
+pub mod utils;
+use utils::{prelude::*, MockFungibleToken, MockNonFungibleToken, FOREIGN_USER};
+
+const SIDE: BlockSideLength = 100;
+const TOKEN_COUNT: BlockSideLength = 5000;
+// Mints must cost about the same regardless of a number of NFTs on a board.
+const MAX_GAS_RATIO: u64 = 2;
+
+// A slow gas benchmark, run it with `cargo test -- --ignored`.
+#[ignore]
+#[test]
+fn minting_on_crowded_board() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(FOREIGN_USER, u128::MAX);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        painting: vec![0; SIDE as usize * SIDE as usize],
+        resolution: (SIDE, SIDE).into(),
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    let run_result = pixelboard_program.mint(FOREIGN_USER, vec![0; 1], ((0, 0), (1, 1)).into());
+    let first_mint_gas = run_result.result.main_gas_burned();
+    run_result.succeed(0);
+
+    for token_id in 1..TOKEN_COUNT {
+        let (x, y) = (token_id % SIDE, token_id / SIDE);
+
+        pixelboard_program
+            .mint(FOREIGN_USER, vec![0; 1], ((x, y), (x + 1, y + 1)).into())
+            .succeed(token_id as u128);
+    }
+
+    let (x, y) = (TOKEN_COUNT % SIDE, TOKEN_COUNT / SIDE);
+    let run_result =
+        pixelboard_program.mint(FOREIGN_USER, vec![0; 1], ((x, y), (x + 1, y + 1)).into());
+    let last_mint_gas = run_result.result.main_gas_burned();
+    run_result.succeed(TOKEN_COUNT as u128);
+
+    let run_result = pixelboard_program.mint(FOREIGN_USER, vec![0; 1], ((0, 0), (1, 1)).into());
+    let collision_gas = run_result.result.main_gas_burned();
+    run_result.failed(NFTPixelboardError::CoordinatesCollision);
+
+    assert!(last_mint_gas.0 <= first_mint_gas.0 * MAX_GAS_RATIO);
+    assert!(collision_gas.0 <= first_mint_gas.0 * MAX_GAS_RATIO);
+}
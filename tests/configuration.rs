@@ -0,0 +1,194 @@
+pub mod utils;
+use utils::{prelude::*, MockFungibleToken, MockNonFungibleToken, FOREIGN_USER, OWNER, USER};
+
+#[test]
+fn configuration() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25 + 25);
+    ft_program.mint(USER[1], 25);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program =
+        NFTPixelboard::initialize(&system, ft_program.actor_id(), nft_program.actor_id());
+
+    // Should fail because USER[0] isn't the owner of the pixelboard.
+    pixelboard_program
+        .update_config(USER[0], ConfigUpdate::PixelPrice(1))
+        .failed(NFTPixelboardError::NotOwner);
+    // Should fail because `pixel_price` mustn't be more than `MAX_PIXEL_PRICE`.
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::PixelPrice(MAX_PIXEL_PRICE + 1))
+        .failed(NFTPixelboardError::PixelPriceExceeded);
+    // Should fail because `commission_percentage` mustn't be more than 100.
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::CommissionPercentage(101))
+        .failed(NFTPixelboardError::WrongCommissionPercentage);
+
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::PixelPrice(1))
+        .succeed(NFTPixelboardEvent::PixelPriceUpdated(1));
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::CommissionPercentage(0))
+        .succeed(NFTPixelboardEvent::CommissionPercentageUpdated(0));
+
+    // The new pixel price is used for minting.
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+    pixelboard_program.withdraw(OWNER, 25).succeed(25);
+    assert_eq!(ft_program.balance(USER[0]), 25);
+    assert_eq!(ft_program.balance(OWNER), 25);
+
+    // Should fail because `owner` mustn't be `ActorId::zero()`.
+    pixelboard_program
+        .propose_owner(OWNER, 0)
+        .failed(NFTPixelboardError::ZeroAddress);
+    // Should fail because USER[0] isn't the owner of the pixelboard.
+    pixelboard_program
+        .propose_owner(USER[0], USER[0])
+        .failed(NFTPixelboardError::NotOwner);
+
+    pixelboard_program
+        .propose_owner(OWNER, USER[0])
+        .succeed(USER[0]);
+    // A wrong proposal can be replaced by the owner.
+    pixelboard_program
+        .propose_owner(OWNER, FOREIGN_USER)
+        .succeed(FOREIGN_USER);
+    // Should fail because USER[0] isn't the proposed owner anymore.
+    pixelboard_program
+        .accept_ownership(USER[0])
+        .failed(NFTPixelboardError::NotProposedOwner);
+
+    // OWNER keeps admin rights until the ownership is accepted.
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::PixelPrice(1))
+        .succeed(NFTPixelboardEvent::PixelPriceUpdated(1));
+
+    pixelboard_program
+        .accept_ownership(FOREIGN_USER)
+        .succeed(FOREIGN_USER);
+    // Should fail because OWNER isn't the owner of the pixelboard anymore.
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::PixelPrice(2))
+        .failed(NFTPixelboardError::NotOwner);
+    // Should fail because the proposal has already been accepted.
+    pixelboard_program
+        .accept_ownership(FOREIGN_USER)
+        .failed(NFTPixelboardError::NotProposedOwner);
+
+    pixelboard_program.make_offer(USER[1], 0, 1).succeed(0);
+    // Should fail because the pixelboard holds tokens of the offer.
+    pixelboard_program
+        .update_config(FOREIGN_USER, ConfigUpdate::FTProgram(USER[0].into()))
+        .failed(NFTPixelboardError::FundsInEscrow);
+
+    pixelboard_program.cancel_offer(USER[1], 0).succeed(0);
+    // Should fail because the pixelboard holds tokens of the cancelled offer.
+    pixelboard_program
+        .update_config(FOREIGN_USER, ConfigUpdate::FTProgram(USER[0].into()))
+        .failed(NFTPixelboardError::FundsInEscrow);
+
+    pixelboard_program.withdraw(USER[1], 25).succeed(25);
+    pixelboard_program
+        .update_config(FOREIGN_USER, ConfigUpdate::FTProgram(USER[0].into()))
+        .succeed(NFTPixelboardEvent::FTProgramUpdated(USER[0].into()));
+}
+
+#[test]
+fn pausing() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], MAX_PIXEL_PRICE * 50);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program =
+        NFTPixelboard::initialize(&system, ft_program.actor_id(), nft_program.actor_id());
+
+    // Should fail because USER[0] isn't the owner of the pixelboard.
+    pixelboard_program
+        .pause(USER[0], ActionKind::Minting)
+        .failed(NFTPixelboardError::NotOwner);
+
+    pixelboard_program
+        .pause(OWNER, ActionKind::Minting)
+        .succeed(ActionKind::Minting);
+    // Should fail because minting is paused.
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .failed(NFTPixelboardError::Paused);
+
+    pixelboard_program
+        .unpause(OWNER, ActionKind::Minting)
+        .succeed(ActionKind::Minting);
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+
+    pixelboard_program
+        .pause(OWNER, ActionKind::Trading)
+        .succeed(ActionKind::Trading);
+    // Should fail because trading is paused.
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(1))
+        .failed(NFTPixelboardError::Paused);
+    // Should fail because trading is paused.
+    pixelboard_program
+        .buy(USER[1], 0)
+        .failed(NFTPixelboardError::Paused);
+
+    // Owner actions are never paused.
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::PixelPrice(1))
+        .succeed(NFTPixelboardEvent::PixelPriceUpdated(1));
+
+    // Painting isn't affected by paused trading.
+    pixelboard_program.paint(USER[0], 0, vec![1; 25]).succeed(0);
+}
+
+#[test]
+fn pausing_with_pending_tx() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(OWNER, 25);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program = NFTPixelboard::initialize_custom(
+        &system,
+        default_config(ft_program.actor_id(), nft_program.actor_id()),
+    )
+    .succeed();
+
+    // The NFT can be neither transferred nor burned, so the mint of the owner
+    // stays pending.
+    nft_program.fail_transfer(true);
+    nft_program.fail_burn(true);
+    pixelboard_program
+        .mint(OWNER, vec![0; 25], ((0, 0), (5, 5)).into())
+        .failed(NFTPixelboardError::NFTBurnFailed);
+    nft_program.fail_transfer(false);
+    nft_program.fail_burn(false);
+
+    // The pending transaction doesn't block administration.
+    pixelboard_program
+        .pause(OWNER, ActionKind::Minting)
+        .succeed(ActionKind::Minting);
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::PixelPrice(2))
+        .succeed(NFTPixelboardEvent::PixelPriceUpdated(2));
+    // Should fail because minting is paused.
+    pixelboard_program
+        .continue_tx(OWNER)
+        .failed(NFTPixelboardError::Paused);
+
+    pixelboard_program
+        .unpause(OWNER, ActionKind::Minting)
+        .succeed(ActionKind::Minting);
+    pixelboard_program
+        .continue_tx(OWNER)
+        .succeed(NFTPixelboardEvent::Minted(0u128.into()));
+}
@@ -20,7 +20,10 @@ fn initialization_failures() {
         painting: vec![0; 100],
         pixel_price: MAX_PIXEL_PRICE,
         commission_percentage: 100,
+        royalty_percentage: 0,
+        pricing_curve: None,
         resolution: (10, 10).into(),
+        payment_mode: PaymentMode::FungibleToken,
     };
 
     let mut failed_pixelboard_config = pixelboard_config.clone();
@@ -92,6 +95,12 @@ fn initialization_failures() {
     NFTPixelboard::initialize_custom(&system, failed_pixelboard_config)
         .failed(NFTPixelboardError::WrongCommissionPercentage);
 
+    failed_pixelboard_config = pixelboard_config.clone();
+    failed_pixelboard_config.royalty_percentage = 101;
+    // Should fail because `royalty_percentage` mustn't be more than 100.
+    NFTPixelboard::initialize_custom(&system, failed_pixelboard_config)
+        .failed(NFTPixelboardError::WrongRoyaltyPercentage);
+
     failed_pixelboard_config = pixelboard_config;
     failed_pixelboard_config.pixel_price = MAX_PIXEL_PRICE + 1;
     // Should fail because `pixel_price` mustn't be more than `MAX_PIXEL_PRICE`.
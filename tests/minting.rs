@@ -1,7 +1,11 @@
 use gear_lib::non_fungible_token::token::TokenMetadata;
+use gstd::ActorId;
 
 pub mod utils;
-use utils::{prelude::*, FungibleToken, NonFungibleToken, FOREIGN_USER, OWNER};
+use utils::{
+    prelude::*, FungibleToken, MockFungibleToken, MockNonFungibleToken, NonFungibleToken,
+    FOREIGN_USER, OWNER, USER,
+};
 
 // # TODO:: remove ignore after fixing tests
 #[ignore]
@@ -22,7 +26,10 @@ fn minting_failures() {
         painting: vec![0; 100],
         pixel_price: MAX_PIXEL_PRICE,
         commission_percentage: 100,
+        royalty_percentage: 0,
+        pricing_curve: None,
         resolution: (10, 10).into(),
+        payment_mode: PaymentMode::FungibleToken,
     };
     let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
     let default_painting = vec![0; 36];
@@ -162,6 +169,7 @@ fn minting_n_meta_state() {
             owner: FOREIGN_USER.into(),
             pixel_price: None,
             token_id: Some(0.into()),
+            currency: None,
         },
     );
 
@@ -169,6 +177,9 @@ fn minting_n_meta_state() {
         .mint(FOREIGN_USER, vec![0; 6], token.0)
         .succeed(0);
 
+    pixelboard_program
+        .withdraw(OWNER, MAX_PIXEL_PRICE * 6)
+        .succeed(MAX_PIXEL_PRICE * 6);
     ft_program
         .balance(FOREIGN_USER)
         .succeed(MAX_PIXEL_PRICE * (25 + 1));
@@ -269,6 +280,9 @@ fn minting_n_meta_state() {
         .mint(FOREIGN_USER, vec![0], token.0)
         .succeed(2);
 
+    pixelboard_program
+        .withdraw(OWNER, MAX_PIXEL_PRICE * (25 + 1))
+        .succeed(MAX_PIXEL_PRICE * (25 + 1));
     ft_program.balance(FOREIGN_USER).succeed(0);
     ft_program
         .balance(OWNER)
@@ -297,3 +311,305 @@ fn minting_n_meta_state() {
     //     .pixel_info((10, 8).into())
     //     .check(Token::default());
 }
+
+#[test]
+fn minting_rollback() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(FOREIGN_USER, MAX_PIXEL_PRICE * 25);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program =
+        NFTPixelboard::initialize(&system, ft_program.actor_id(), nft_program.actor_id());
+    let rectangle = ((3, 3), (8, 8)).into();
+
+    // Should fail because USER[0] doesn't have enough fungible tokens.
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], rectangle)
+        .failed(NFTPixelboardError::FTokensTransferFailed);
+
+    nft_program.fail_mint(true);
+    // Should fail because the NFT program fails to mint the NFT.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 25], rectangle)
+        .failed(NFTPixelboardError::NFTMintFailed);
+    nft_program.fail_mint(false);
+
+    // The payment should be refunded.
+    assert_eq!(ft_program.balance(FOREIGN_USER), MAX_PIXEL_PRICE * 25);
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 0);
+
+    nft_program.fail_transfer(true);
+    // Should fail because the NFT program fails to transfer the minted NFT.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 25], rectangle)
+        .failed(NFTPixelboardError::NFTTransferFailed);
+    nft_program.fail_transfer(false);
+
+    // The minted NFT should be burned & the payment should be refunded.
+    assert_eq!(nft_program.owner(0), None);
+    assert_eq!(ft_program.balance(FOREIGN_USER), MAX_PIXEL_PRICE * 25);
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 0);
+
+    // The rectangle should be released, so it can be minted again.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 25], rectangle)
+        .succeed(1);
+
+    assert_eq!(nft_program.owner(1), Some(FOREIGN_USER.into()));
+    pixelboard_program
+        .withdraw(OWNER, MAX_PIXEL_PRICE * 25)
+        .succeed(MAX_PIXEL_PRICE * 25);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 0);
+    assert_eq!(ft_program.balance(OWNER), MAX_PIXEL_PRICE * 25);
+}
+
+#[test]
+fn occupancy() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(FOREIGN_USER, 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        block_side_length: 2,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 16], ((2, 2), (6, 6)).into())
+        .succeed(0);
+    // Should fail because the rectangle overlaps the NFT.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 16], ((4, 4), (8, 8)).into())
+        .failed(NFTPixelboardError::CoordinatesCollision);
+
+    pixelboard_program
+        .split(FOREIGN_USER, 0, SplitLine::Vertical(4))
+        .succeed((1, 2));
+    // Should fail because the rectangle overlaps the right part.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 16], ((4, 4), (8, 8)).into())
+        .failed(NFTPixelboardError::CoordinatesCollision);
+
+    pixelboard_program.burn(FOREIGN_USER, 2, false).succeed(2);
+    // Should fail because the rectangle overlaps the left part.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 4], ((2, 2), (4, 4)).into())
+        .failed(NFTPixelboardError::CoordinatesCollision);
+
+    // The area of the burned right part can be minted again.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 16], ((4, 4), (8, 8)).into())
+        .succeed(3);
+}
+
+#[test]
+fn pixel_info() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(FOREIGN_USER, 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = default_config(ft_program.actor_id(), nft_program.actor_id());
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    // Row & column bands whose bottom right corners lie past points of each
+    // other.
+    let rectangles: [Rectangle; 4] = [
+        ((0, 0), (10, 2)).into(),
+        ((0, 2), (2, 10)).into(),
+        ((2, 2), (10, 4)).into(),
+        ((4, 4), (6, 10)).into(),
+    ];
+
+    for (token_id, rectangle) in rectangles.into_iter().enumerate() {
+        pixelboard_program
+            .mint(FOREIGN_USER, vec![0; rectangle.area() as usize], rectangle)
+            .succeed(token_id as u128);
+    }
+
+    let token = |token_id: usize| {
+        Some(Token(
+            rectangles[token_id],
+            TokenInfo {
+                token_id: Some((token_id as u128).into()),
+                owner: FOREIGN_USER.into(),
+                pixel_price: None,
+                currency: None,
+            },
+        ))
+    };
+
+    for (coordinates, expected_token) in [
+        ((0, 0), token(0)),
+        ((9, 1), token(0)),
+        ((1, 2), token(1)),
+        ((1, 9), token(1)),
+        ((2, 2), token(2)),
+        ((9, 3), token(2)),
+        ((4, 4), token(3)),
+        ((5, 9), token(3)),
+        // Free pixels between the bands.
+        ((3, 5), None),
+        ((6, 4), None),
+        ((9, 9), None),
+        // Pixels out of the canvas.
+        ((10, 0), None),
+        ((0, 10), None),
+    ] {
+        pixelboard_program
+            .pixel_info(coordinates.into())
+            .eq(expected_token);
+    }
+}
+
+#[test]
+fn batch_minting() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(FOREIGN_USER, 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = default_config(ft_program.actor_id(), nft_program.actor_id());
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 4], ((0, 0), (2, 2)).into())
+        .succeed(0);
+
+    // Should fail because the batch is empty.
+    pixelboard_program
+        .mint_batch(FOREIGN_USER, vec![])
+        .failed(NFTPixelboardError::NotEnoughNFTs);
+    // Should fail because the rectangles collide with each other.
+    pixelboard_program
+        .mint_batch(
+            FOREIGN_USER,
+            vec![
+                (((2, 0), (4, 2)).into(), vec![0; 4]),
+                (((3, 1), (5, 3)).into(), vec![0; 4]),
+            ],
+        )
+        .failed(NFTPixelboardError::CoordinatesCollision);
+    // Should fail because the second rectangle collides with the minted one.
+    pixelboard_program
+        .mint_batch(
+            FOREIGN_USER,
+            vec![
+                (((2, 0), (4, 2)).into(), vec![0; 4]),
+                (((1, 1), (3, 3)).into(), vec![0; 4]),
+            ],
+        )
+        .failed(NFTPixelboardError::CoordinatesCollision);
+    // Should fail because the second painting has a wrong length.
+    pixelboard_program
+        .mint_batch(
+            FOREIGN_USER,
+            vec![
+                (((2, 0), (4, 2)).into(), vec![0; 4]),
+                (((0, 2), (4, 4)).into(), vec![0; 4]),
+            ],
+        )
+        .failed(NFTPixelboardError::WrongPaintingLength);
+
+    let l_shape = vec![
+        (((2, 0), (4, 2)).into(), vec![0; 4]),
+        (((0, 2), (4, 4)).into(), vec![0; 8]),
+    ];
+
+    nft_program.fail_mint(true);
+    // Should fail because the NFT program fails to mint NFTs.
+    pixelboard_program
+        .mint_batch(FOREIGN_USER, l_shape.clone())
+        .failed(NFTPixelboardError::NFTMintFailed);
+    nft_program.fail_mint(false);
+
+    nft_program.fail_transfer(true);
+    // Should fail because the NFT program fails to transfer minted NFTs.
+    pixelboard_program
+        .mint_batch(FOREIGN_USER, l_shape.clone())
+        .failed(NFTPixelboardError::NFTTransferFailed);
+    nft_program.fail_transfer(false);
+
+    // Minted NFTs should be burned & the payment should be refunded.
+    assert_eq!(nft_program.owner(1), None);
+    assert_eq!(nft_program.owner(2), None);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 96);
+
+    // Rectangles of failed batches should be released, so they can be minted
+    // with one payment.
+    pixelboard_program
+        .mint_batch(FOREIGN_USER, l_shape)
+        .succeed(vec![3, 4]);
+    assert_eq!(nft_program.owner(3), Some(FOREIGN_USER.into()));
+    assert_eq!(nft_program.owner(4), Some(FOREIGN_USER.into()));
+    assert_eq!(ft_program.balance(FOREIGN_USER), 84);
+    pixelboard_program.withdraw(OWNER, 16).succeed(16);
+}
+
+#[test]
+fn batch_minting_continuation() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(FOREIGN_USER, 12);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program = NFTPixelboard::initialize_custom(
+        &system,
+        default_config(ft_program.actor_id(), nft_program.actor_id()),
+    )
+    .succeed();
+
+    let tokens = vec![
+        (((0, 0), (2, 2)).into(), vec![0; 4]),
+        (((2, 0), (4, 2)).into(), vec![0; 4]),
+        (((4, 0), (6, 2)).into(), vec![0; 4]),
+    ];
+
+    nft_program.fail_transfer_of(1, true);
+    // Should fail because the NFT program fails to transfer the 2nd NFT.
+    pixelboard_program
+        .mint_batch(FOREIGN_USER, tokens)
+        .failed(NFTPixelboardError::NFTTransferFailed);
+    nft_program.fail_transfer_of(1, false);
+
+    // The 1st NFT is delivered, so the batch stays pending with its payment
+    // instead of being rolled back.
+    assert_eq!(nft_program.owner(0), Some(FOREIGN_USER.into()));
+    // The mock NFT program records minted NFTs as owned by a zero address.
+    assert_eq!(nft_program.owner(1), Some(ActorId::zero()));
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 12);
+    // Should fail because the 1st NFT can't be taken back from FOREIGN_USER.
+    pixelboard_program
+        .cancel_tx(OWNER, FOREIGN_USER)
+        .failed(NFTPixelboardError::TxNotCancellable);
+    // Should fail because the batch's rectangles are still reserved.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 4], ((0, 0), (2, 2)).into())
+        .failed(NFTPixelboardError::PreviousTxMustBeCompleted);
+
+    pixelboard_program
+        .continue_tx(FOREIGN_USER)
+        .succeed(NFTPixelboardEvent::MintedBatch(
+            (0..3u128).map(Into::into).collect(),
+        ));
+    for token_id in 0..3 {
+        assert_eq!(nft_program.owner(token_id), Some(FOREIGN_USER.into()));
+    }
+    // The batch is paid only once.
+    assert_eq!(ft_program.balance(FOREIGN_USER), 0);
+    pixelboard_program.withdraw(OWNER, 12).succeed(12);
+    pixelboard_program
+        .continue_tx(FOREIGN_USER)
+        .failed(NFTPixelboardError::TxNotFound);
+}
@@ -0,0 +1,147 @@
+pub mod utils;
+use utils::{prelude::*, MockFungibleToken, MockNonFungibleToken, FOREIGN_USER, OWNER, USER};
+
+#[test]
+fn offers() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 100);
+    ft_program.mint(FOREIGN_USER, 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 13,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program =
+        NFTPixelboard::initialize_custom(&system, pixelboard_config.clone()).succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+    // Offers can be made for an NFT that isn't for sale.
+    pixelboard_program.make_offer(USER[1], 0, 2).succeed(0);
+    pixelboard_program.make_offer(FOREIGN_USER, 0, 3).succeed(0);
+    assert_eq!(ft_program.balance(USER[1]), 100 - 25 * 2);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 100 - 25 * 3);
+
+    // Replacing an offer should credit the previous one.
+    pixelboard_program.make_offer(USER[1], 0, 1).succeed(0);
+    assert_eq!(ft_program.balance(USER[1]), 100 - 25 * 2 - 25);
+
+    pixelboard_program.cancel_offer(USER[1], 0).succeed(0);
+    // Should fail because USER[1] has only 75 on its balance.
+    pixelboard_program
+        .withdraw(USER[1], 76)
+        .failed(NFTPixelboardError::InsufficientBalance);
+    pixelboard_program.withdraw(USER[1], 75).succeed(75);
+    assert_eq!(ft_program.balance(USER[1]), 100);
+
+    // The NFT is put up for sale, so the pixelboard program can transfer it.
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(4))
+        .succeed(0);
+    pixelboard_program
+        .accept_offer(USER[0], 0, FOREIGN_USER)
+        .succeed(0);
+
+    let token_price = 25 * 3;
+    let commission = token_price * pixelboard_config.commission_percentage as u128 / 100;
+    pixelboard_program
+        .withdraw(OWNER, 25 + commission)
+        .succeed(25 + commission);
+    pixelboard_program
+        .withdraw(USER[0], token_price - commission)
+        .succeed(token_price - commission);
+    assert_eq!(ft_program.balance(OWNER), 25 + commission);
+    assert_eq!(ft_program.balance(USER[0]), token_price - commission);
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 0);
+    assert_eq!(nft_program.owner(0), Some(FOREIGN_USER.into()));
+}
+
+#[test]
+fn accepting_offer_for_nft_not_on_sale() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 50);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program = NFTPixelboard::initialize_custom(
+        &system,
+        default_config(ft_program.actor_id(), nft_program.actor_id()),
+    )
+    .succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+    pixelboard_program.make_offer(USER[1], 0, 2).succeed(0);
+
+    // The NFT is held by USER[0], so it's transferred from them directly.
+    assert_eq!(nft_program.owner(0), Some(USER[0].into()));
+    pixelboard_program
+        .accept_offer(USER[0], 0, USER[1])
+        .succeed(0);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+
+    // The offer is removed after it's accepted.
+    pixelboard_program
+        .cancel_offer(USER[1], 0)
+        .failed(NFTPixelboardError::OfferNotFound);
+    pixelboard_program.withdraw(USER[0], 50).succeed(50);
+    assert_eq!(ft_program.balance(USER[0]), 50);
+    assert_eq!(ft_program.balance(USER[1]), 0);
+}
+
+#[test]
+fn offers_failures() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 25);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 13,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+
+    // Should fail because USER[0] is the owner of the NFT.
+    pixelboard_program
+        .make_offer(USER[0], 0, 1)
+        .failed(NFTPixelboardError::OwnNFT);
+    // Should fail because `pixel_price` mustn't be more than `MAX_PIXEL_PRICE`.
+    pixelboard_program
+        .make_offer(USER[1], 0, MAX_PIXEL_PRICE + 1)
+        .failed(NFTPixelboardError::PixelPriceExceeded);
+    // Should fail because USER[1] doesn't have enough fungible tokens.
+    pixelboard_program
+        .make_offer(USER[1], 0, 2)
+        .failed(NFTPixelboardError::FTokensTransferFailed);
+    // Should fail because USER[1] doesn't have an offer.
+    pixelboard_program
+        .cancel_offer(USER[1], 0)
+        .failed(NFTPixelboardError::OfferNotFound);
+    pixelboard_program
+        .accept_offer(USER[0], 0, USER[1])
+        .failed(NFTPixelboardError::OfferNotFound);
+
+    pixelboard_program.make_offer(USER[1], 0, 1).succeed(0);
+
+    // Should fail because FOREIGN_USER isn't the owner of the NFT.
+    pixelboard_program
+        .accept_offer(FOREIGN_USER, 0, USER[1])
+        .failed(NFTPixelboardError::NotOwner);
+}
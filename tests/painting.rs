@@ -166,7 +166,10 @@ fn painting() {
         painting: vec![0],
         pixel_price: MAX_PIXEL_PRICE,
         commission_percentage: 100,
+        royalty_percentage: 0,
+        pricing_curve: None,
         resolution: (1, 1).into(),
+        payment_mode: PaymentMode::FungibleToken,
     };
     pixelboard_program =
         NFTPixelboard::initialize_custom(&system, pixelboard_config.clone()).succeed();
@@ -0,0 +1,462 @@
+use gstd::ActorId;
+
+pub mod utils;
+use utils::{prelude::*, MockFungibleToken, MockNonFungibleToken, FOREIGN_USER, OWNER, USER};
+
+#[test]
+fn value_payments() {
+    let system = utils::initialize_system();
+    system.mint_to(USER[0], 10_000);
+    system.mint_to(USER[1], 10_000);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        pixel_price: 100,
+        commission_percentage: 10,
+        payment_mode: PaymentMode::Value,
+        ..default_config(ActorId::zero(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+    let rectangle = ((3, 3), (8, 8)).into();
+
+    // Should fail because the attached value isn't enough to mint the NFT.
+    pixelboard_program
+        .mint_with_value(USER[0], vec![0; 25], rectangle, 2_499)
+        .failed(NFTPixelboardError::NotEnoughValue);
+    // Overpayment should be returned with the reply.
+    pixelboard_program
+        .mint_with_value(USER[0], vec![0; 25], rectangle, 3_000)
+        .succeed(0);
+    assert_eq!(nft_program.owner(0), Some(USER[0].into()));
+
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(200))
+        .succeed(0);
+
+    // Should fail because the attached value isn't enough to buy the NFT.
+    pixelboard_program
+        .buy_with_value(USER[1], 0, 4_999)
+        .failed(NFTPixelboardError::NotEnoughValue);
+    pixelboard_program
+        .buy_with_value(USER[1], 0, 5_000)
+        .succeed(0);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+}
+
+#[test]
+fn value_payments_continuation() {
+    let system = utils::initialize_system();
+    system.mint_to(USER[0], 10_000);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        pixel_price: 100,
+        payment_mode: PaymentMode::Value,
+        ..default_config(ActorId::zero(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+    let rectangle = ((3, 3), (8, 8)).into();
+
+    // The NFT can be neither transferred nor burned, so the mint stays pending
+    // with its payment.
+    nft_program.fail_transfer(true);
+    nft_program.fail_burn(true);
+    pixelboard_program
+        .mint_with_value(USER[0], vec![0; 25], rectangle, 2_500)
+        .failed(NFTPixelboardError::NFTBurnFailed);
+    nft_program.fail_burn(false);
+
+    // The rectangle stays reserved by the pending mint.
+    pixelboard_program
+        .mint(USER[1], vec![0; 25], rectangle)
+        .failed(NFTPixelboardError::CoordinatesCollision);
+
+    // The payment was taken by the interrupted attempt, so the continuation
+    // doesn't require any value.
+    nft_program.fail_transfer(false);
+    pixelboard_program
+        .continue_tx(USER[0])
+        .succeed(NFTPixelboardEvent::Minted(0u128.into()));
+    assert_eq!(nft_program.owner(0), Some(USER[0].into()));
+    pixelboard_program
+        .continue_tx(USER[0])
+        .failed(NFTPixelboardError::TxNotFound);
+}
+
+#[test]
+fn multiple_currencies() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    let other_ft_program = MockFungibleToken::initialize(&system);
+    other_ft_program.mint(USER[0], 50);
+    other_ft_program.mint(USER[1], 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 10,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+    let other_currency = other_ft_program.actor_id();
+    let rectangle = ((3, 3), (8, 8)).into();
+
+    // Should fail because the currency isn't accepted yet.
+    pixelboard_program
+        .mint_in_currency(USER[0], vec![0; 25], rectangle, other_currency)
+        .failed(NFTPixelboardError::UnknownCurrency);
+
+    // Should fail because USER[0] isn't the owner of the pixelboard.
+    pixelboard_program
+        .update_config(
+            USER[0],
+            ConfigUpdate::AddCurrency {
+                ft_program: other_currency,
+                pixel_price: 2,
+            },
+        )
+        .failed(NFTPixelboardError::NotOwner);
+    pixelboard_program
+        .update_config(
+            OWNER,
+            ConfigUpdate::AddCurrency {
+                ft_program: other_currency,
+                pixel_price: 2,
+            },
+        )
+        .succeed(NFTPixelboardEvent::CurrencyAdded(other_currency));
+
+    // The mint should be paid at the price of the other currency.
+    pixelboard_program
+        .mint_in_currency(USER[0], vec![0; 25], rectangle, other_currency)
+        .succeed(0);
+    pixelboard_program
+        .withdraw_in_currency(OWNER, other_currency, 50)
+        .succeed(50);
+    assert_eq!(other_ft_program.balance(USER[0]), 0);
+    assert_eq!(other_ft_program.balance(OWNER), 50);
+
+    pixelboard_program
+        .change_sale_state_in_currency(USER[0], 0, 4, other_currency)
+        .succeed(0);
+
+    // Should fail because the NFT is listed in the other currency.
+    pixelboard_program
+        .buy(USER[1], 0)
+        .failed(NFTPixelboardError::WrongCurrency);
+
+    pixelboard_program
+        .buy_in_currency(USER[1], 0, other_currency)
+        .succeed(0);
+    pixelboard_program
+        .withdraw_in_currency(OWNER, other_currency, 10)
+        .succeed(10);
+    pixelboard_program
+        .withdraw_in_currency(USER[0], other_currency, 90)
+        .succeed(90);
+    assert_eq!(other_ft_program.balance(USER[1]), 0);
+    assert_eq!(other_ft_program.balance(OWNER), 50 + 10);
+    assert_eq!(other_ft_program.balance(USER[0]), 90);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::RemoveCurrency(other_currency))
+        .succeed(NFTPixelboardEvent::CurrencyRemoved(other_currency));
+
+    // Should fail because the currency isn't accepted anymore.
+    pixelboard_program
+        .change_sale_state_in_currency(USER[1], 0, 4, other_currency)
+        .failed(NFTPixelboardError::UnknownCurrency);
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::RemoveCurrency(other_currency))
+        .failed(NFTPixelboardError::UnknownCurrency);
+}
+
+#[test]
+fn withdrawals() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 50);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 10,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(2))
+        .succeed(0);
+
+    // A seller that can't receive fungible tokens shouldn't block the purchase.
+    ft_program.fail_transfers_to(USER[0], true);
+    pixelboard_program.buy(USER[1], 0).succeed(0);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+
+    // Should fail because the FT program fails to transfer tokens to USER[0].
+    pixelboard_program
+        .withdraw(USER[0], 45)
+        .failed(NFTPixelboardError::FTokensTransferFailed);
+    ft_program.fail_transfers_to(USER[0], false);
+
+    // Should fail because USER[0] doesn't have enough tokens on its balance.
+    pixelboard_program
+        .withdraw(USER[0], 46)
+        .failed(NFTPixelboardError::InsufficientBalance);
+
+    // The balance should stay credited after the failed withdrawal.
+    pixelboard_program.withdraw(USER[0], 45).succeed(45);
+    pixelboard_program.withdraw(OWNER, 30).succeed(30);
+    assert_eq!(ft_program.balance(USER[0]), 45);
+    assert_eq!(ft_program.balance(OWNER), 30);
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 0);
+
+    // Should fail because the balance of USER[0] is already withdrawn.
+    pixelboard_program
+        .withdraw(USER[0], 1)
+        .failed(NFTPixelboardError::InsufficientBalance);
+}
+
+#[test]
+fn beneficiaries() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(FOREIGN_USER, 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 10,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    // Should fail because USER[0] isn't the owner of the pixelboard.
+    pixelboard_program
+        .update_config(
+            USER[0],
+            ConfigUpdate::Beneficiaries(vec![(USER[0].into(), 100)]),
+        )
+        .failed(NFTPixelboardError::NotOwner);
+    // Should fail because shares don't sum up to 100.
+    pixelboard_program
+        .update_config(
+            OWNER,
+            ConfigUpdate::Beneficiaries(vec![(USER[0].into(), 70), (USER[1].into(), 20)]),
+        )
+        .failed(NFTPixelboardError::WrongBeneficiaryShares);
+    // Should fail because a beneficiary mustn't be `ActorId::zero()`.
+    pixelboard_program
+        .update_config(
+            OWNER,
+            ConfigUpdate::Beneficiaries(vec![(USER[0].into(), 70), (ActorId::zero(), 30)]),
+        )
+        .failed(NFTPixelboardError::ZeroAddress);
+
+    pixelboard_program
+        .update_config(
+            OWNER,
+            ConfigUpdate::Beneficiaries(vec![(USER[0].into(), 70), (USER[1].into(), 30)]),
+        )
+        .succeed(NFTPixelboardEvent::BeneficiariesUpdated);
+
+    // 7 tokens are split into 4 & 2, and the remainder goes to USER[0].
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 7], ((0, 0), (7, 1)).into())
+        .succeed(0);
+    pixelboard_program.withdraw(USER[0], 5).succeed(5);
+    pixelboard_program.withdraw(USER[1], 2).succeed(2);
+    pixelboard_program
+        .withdraw(OWNER, 1)
+        .failed(NFTPixelboardError::InsufficientBalance);
+
+    // An empty list makes the owner the only beneficiary again.
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::Beneficiaries(vec![]))
+        .succeed(NFTPixelboardEvent::BeneficiariesUpdated);
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 3], ((0, 1), (3, 2)).into())
+        .succeed(1);
+    pixelboard_program.withdraw(OWNER, 3).succeed(3);
+}
+
+#[test]
+fn pricing_zones() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(FOREIGN_USER, 100);
+
+    let foreign_ft_program = MockFungibleToken::initialize(&system);
+    foreign_ft_program.mint(FOREIGN_USER, 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 10,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+    pixelboard_program
+        .update_config(
+            OWNER,
+            ConfigUpdate::AddCurrency {
+                ft_program: foreign_ft_program.actor_id(),
+                pixel_price: 2,
+            },
+        )
+        .succeed(NFTPixelboardEvent::CurrencyAdded(
+            foreign_ft_program.actor_id(),
+        ));
+
+    let zone = PricingZone {
+        rectangle: ((0, 0), (2, 2)).into(),
+        pixel_price: 5,
+    };
+
+    // Should fail because USER[0] isn't the owner of the pixelboard.
+    pixelboard_program
+        .update_config(USER[0], ConfigUpdate::PricingZones(vec![zone]))
+        .failed(NFTPixelboardError::NotOwner);
+    // Should fail because zones mustn't overlap.
+    pixelboard_program
+        .update_config(
+            OWNER,
+            ConfigUpdate::PricingZones(vec![
+                zone,
+                PricingZone {
+                    rectangle: ((1, 1), (3, 3)).into(),
+                    pixel_price: 5,
+                },
+            ]),
+        )
+        .failed(NFTPixelboardError::ZonesOverlap);
+    // Should fail because a zone mustn't be out of the canvas.
+    pixelboard_program
+        .update_config(
+            OWNER,
+            ConfigUpdate::PricingZones(vec![PricingZone {
+                rectangle: ((5, 5), (11, 6)).into(),
+                pixel_price: 5,
+            }]),
+        )
+        .failed(NFTPixelboardError::CoordinatesOutOfCanvas);
+    // Should fail because a pixel price of a zone mustn't exceed
+    // `MAX_PIXEL_PRICE`.
+    pixelboard_program
+        .update_config(
+            OWNER,
+            ConfigUpdate::PricingZones(vec![PricingZone {
+                rectangle: ((5, 5), (6, 6)).into(),
+                pixel_price: MAX_PIXEL_PRICE + 1,
+            }]),
+        )
+        .failed(NFTPixelboardError::PixelPriceExceeded);
+
+    pixelboard_program
+        .update_config(OWNER, ConfigUpdate::PricingZones(vec![zone]))
+        .succeed(NFTPixelboardEvent::PricingZonesUpdated);
+
+    // Should fail because a rectangle intersecting a zone can be minted only in
+    // the default currency.
+    pixelboard_program
+        .mint_in_currency(
+            FOREIGN_USER,
+            vec![0; 2],
+            ((1, 0), (3, 1)).into(),
+            foreign_ft_program.actor_id(),
+        )
+        .failed(NFTPixelboardError::WrongCurrency);
+
+    // 1 pixel in the zone costs 5 tokens, and 1 pixel out of it costs 1.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 2], ((1, 0), (3, 1)).into())
+        .succeed(0);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 94);
+    pixelboard_program.withdraw(OWNER, 6).succeed(6);
+
+    // Rectangles outside zones are still priced in any currency.
+    pixelboard_program
+        .mint_in_currency(
+            FOREIGN_USER,
+            vec![0; 2],
+            ((3, 0), (5, 1)).into(),
+            foreign_ft_program.actor_id(),
+        )
+        .succeed(1);
+    assert_eq!(foreign_ft_program.balance(FOREIGN_USER), 96);
+}
+
+#[test]
+fn pricing_curves() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(FOREIGN_USER, 100_000);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let mut pixelboard_config = InitNFTPixelboard {
+        pixel_price: 100,
+        commission_percentage: 10,
+        pricing_curve: Some(PricingCurve::Linear { slope: 2 }),
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program =
+        NFTPixelboard::initialize_custom(&system, pixelboard_config.clone()).succeed();
+
+    // The first half of the canvas is minted at the initial price.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 50], ((0, 0), (10, 5)).into())
+        .succeed(0);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 95_000);
+
+    // 50 minted percents add 2 * 50 = 100 percents of the initial price.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 1], ((0, 5), (1, 6)).into())
+        .succeed(1);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 94_800);
+
+    pixelboard_config.pricing_curve = Some(PricingCurve::Exponential { rate: 10 });
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 50], ((0, 0), (10, 5)).into())
+        .succeed(2);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 89_800);
+
+    // The price rises by 10 percents 50 times with rounding down each time.
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 1], ((0, 5), (1, 6)).into())
+        .succeed(3);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 78_434);
+
+    // Burned NFTs no longer count as minted.
+    pixelboard_program.burn(FOREIGN_USER, 2, false).succeed(2);
+    pixelboard_program.burn(FOREIGN_USER, 3, false).succeed(3);
+    pixelboard_program
+        .mint(FOREIGN_USER, vec![0; 1], ((0, 5), (1, 6)).into())
+        .succeed(4);
+    assert_eq!(ft_program.balance(FOREIGN_USER), 78_334);
+
+    // A raised price is capped at `MAX_PIXEL_PRICE`.
+    assert_eq!(
+        PricingCurve::Exponential { rate: u16::MAX }.pixel_price(MAX_PIXEL_PRICE, 100),
+        MAX_PIXEL_PRICE
+    );
+    assert_eq!(
+        PricingCurve::Linear { slope: u16::MAX }.pixel_price(MAX_PIXEL_PRICE, 100),
+        MAX_PIXEL_PRICE
+    );
+}
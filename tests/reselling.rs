@@ -1,5 +1,8 @@
 pub mod utils;
-use utils::{prelude::*, FungibleToken, NonFungibleToken, FOREIGN_USER, OWNER, USER};
+use utils::{
+    prelude::*, FungibleToken, MockFungibleToken, MockNonFungibleToken, NonFungibleToken,
+    FOREIGN_USER, OWNER, USER,
+};
 
 // # TODO:: remove ignore after fixing tests
 #[ignore]
@@ -21,7 +24,10 @@ fn reselling() {
         painting: vec![0; 100],
         pixel_price: MAX_PIXEL_PRICE,
         commission_percentage: 13,
+        royalty_percentage: 0,
+        pricing_curve: None,
         resolution: (10, 10).into(),
+        payment_mode: PaymentMode::FungibleToken,
     };
     let pixelboard_program =
         NFTPixelboard::initialize_custom(&system, pixelboard_config.clone()).succeed();
@@ -32,6 +38,7 @@ fn reselling() {
             owner: USER[0].into(),
             token_id: Some(0.into()),
             pixel_price: Some(MAX_PIXEL_PRICE),
+            currency: None,
         },
     );
 
@@ -78,6 +85,12 @@ fn reselling() {
     token.1.pixel_price = None;
 
     let commission = 25 * pixelboard_config.commission_percentage as u128 / 100;
+    pixelboard_program
+        .withdraw(OWNER, MAX_PIXEL_PRICE * 25 + commission)
+        .succeed(MAX_PIXEL_PRICE * 25 + commission);
+    pixelboard_program
+        .withdraw(USER[0], 25 - commission)
+        .succeed(25 - commission);
     ft_program
         .balance(OWNER)
         .succeed(MAX_PIXEL_PRICE * 25 + commission);
@@ -107,10 +120,12 @@ fn reselling_failures() {
         painting: vec![0; 100],
         pixel_price: MAX_PIXEL_PRICE,
         commission_percentage: 13,
+        royalty_percentage: 0,
+        pricing_curve: None,
         resolution: (10, 10).into(),
+        payment_mode: PaymentMode::FungibleToken,
     };
-    let pixelboard_program =
-        NFTPixelboard::initialize_custom(&system, pixelboard_config.clone()).succeed();
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
 
     pixelboard_program
         .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
@@ -132,17 +147,254 @@ fn reselling_failures() {
         .change_sale_state(USER[0], 0, Some(MAX_PIXEL_PRICE))
         .succeed(0);
 
-    // Should fail because USER[0] doesn't have enough fungible tokens to buy this NFT.
+    // Should fail because USER[1] doesn't have enough fungible tokens to buy this NFT.
     pixelboard_program
         .buy(USER[1], 0)
         .failed(NFTPixelboardError::FTokensTransferFailed);
 
-    // But a commission should still be debited from USER[0] because USER[0] has enough tokens for it.
-    let commission = MAX_PIXEL_PRICE * 25 * pixelboard_config.commission_percentage as u128 / 100;
-    ft_program
-        .balance(USER[1])
-        .succeed(MAX_PIXEL_PRICE * 24 - commission);
-    ft_program
-        .balance(OWNER)
-        .succeed(MAX_PIXEL_PRICE * 25 + commission);
+    // A commission shouldn't be debited from USER[1] because the purchase failed.
+    pixelboard_program
+        .withdraw(OWNER, MAX_PIXEL_PRICE * 25)
+        .succeed(MAX_PIXEL_PRICE * 25);
+    ft_program.balance(USER[1]).succeed(MAX_PIXEL_PRICE * 24);
+    ft_program.balance(OWNER).succeed(MAX_PIXEL_PRICE * 25);
+}
+
+#[test]
+fn dutch_auction() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 250);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = default_config(ft_program.actor_id(), nft_program.actor_id());
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+
+    // Should fail because `floor_pixel_price` mustn't be more than `start_pixel_price`.
+    pixelboard_program
+        .start_dutch_auction(USER[0], 0, 10, 11, 10)
+        .failed(NFTPixelboardError::WrongFloorPixelPrice);
+    // Should fail because `duration` must be more than 0.
+    pixelboard_program
+        .start_dutch_auction(USER[0], 0, 10, 2, 0)
+        .failed(NFTPixelboardError::ZeroDuration);
+
+    pixelboard_program
+        .start_dutch_auction(USER[0], 0, 10, 2, 8)
+        .succeed(0);
+    // Should fail because the NFT is already for sale.
+    pixelboard_program
+        .start_auction(USER[0], 0, 1, 10)
+        .failed(NFTPixelboardError::NFTIsOnSale);
+
+    // After `duration` blocks the pixel price stays equal to the floor one.
+    system.spend_blocks(10);
+
+    pixelboard_program.buy(USER[1], 0).succeed(0);
+    pixelboard_program.withdraw(USER[0], 25 * 2).succeed(25 * 2);
+    assert_eq!(ft_program.balance(USER[1]), 250 - 25 * 2);
+    assert_eq!(ft_program.balance(USER[0]), 25 * 2);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+}
+
+#[test]
+fn buying_rollback() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 40);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 10,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(2))
+        .succeed(0);
+
+    // Should fail because USER[1] doesn't have enough fungible tokens to buy this NFT.
+    pixelboard_program
+        .buy(USER[1], 0)
+        .failed(NFTPixelboardError::FTokensTransferFailed);
+
+    // Nothing should be debited from USER[1].
+    pixelboard_program.withdraw(OWNER, 25).succeed(25);
+    assert_eq!(ft_program.balance(USER[1]), 40);
+    assert_eq!(ft_program.balance(OWNER), 25);
+
+    ft_program.mint(USER[1], 10);
+    nft_program.fail_transfer(true);
+    // Should fail because the NFT program fails to transfer the NFT.
+    pixelboard_program
+        .buy(USER[1], 0)
+        .failed(NFTPixelboardError::NFTTransferFailed);
+    nft_program.fail_transfer(false);
+
+    // The entire price should be refunded to USER[1].
+    assert_eq!(ft_program.balance(USER[1]), 50);
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 0);
+    assert_eq!(ft_program.balance(OWNER), 25);
+    assert_eq!(nft_program.owner(0), Some(pixelboard_program.actor_id()));
+
+    pixelboard_program.buy(USER[1], 0).succeed(0);
+    pixelboard_program.withdraw(OWNER, 5).succeed(5);
+    pixelboard_program.withdraw(USER[0], 45).succeed(45);
+
+    assert_eq!(ft_program.balance(USER[1]), 0);
+    assert_eq!(ft_program.balance(OWNER), 25 + 5);
+    assert_eq!(ft_program.balance(USER[0]), 45);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+}
+
+#[test]
+fn royalties() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 50);
+    ft_program.mint(USER[1], 100);
+    ft_program.mint(FOREIGN_USER, 200);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 10,
+        royalty_percentage: 5,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    // Should fail because `royalty_percentage` mustn't be more than 100.
+    pixelboard_program
+        .mint_with_royalty(USER[0], vec![0; 25], ((3, 3), (8, 8)).into(), 101)
+        .failed(NFTPixelboardError::WrongRoyaltyPercentage);
+
+    pixelboard_program
+        .mint_with_royalty(USER[0], vec![0; 25], ((3, 3), (8, 8)).into(), 20)
+        .succeed(0);
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((0, 0), (5, 1)).into())
+        .succeed(1);
+    pixelboard_program.withdraw(OWNER, 30).succeed(30);
+
+    // The minter selling its own NFT gets both the royalty & the seller's share.
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(4))
+        .succeed(0);
+    pixelboard_program.buy(USER[1], 0).succeed(0);
+    pixelboard_program.withdraw(OWNER, 10).succeed(10);
+    pixelboard_program.withdraw(USER[0], 90).succeed(90);
+
+    // A resale is split between the owner, the minter & the seller.
+    pixelboard_program
+        .change_sale_state(USER[1], 0, Some(8))
+        .succeed(0);
+    pixelboard_program.buy(FOREIGN_USER, 0).succeed(0);
+    pixelboard_program.withdraw(OWNER, 20).succeed(20);
+    pixelboard_program.withdraw(USER[0], 40).succeed(40);
+    pixelboard_program.withdraw(USER[1], 140).succeed(140);
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 0);
+
+    // NFTs with the same royalty can be merged.
+    pixelboard_program
+        .mint(USER[0], vec![0; 5], ((0, 1), (5, 2)).into())
+        .succeed(2);
+    pixelboard_program.merge(USER[0], vec![1, 2]).succeed(3);
+    pixelboard_program
+        .mint_with_royalty(USER[0], vec![0; 2], ((5, 0), (6, 2)).into(), 20)
+        .succeed(4);
+
+    // Should fail because the NFTs have different royalties.
+    pixelboard_program
+        .merge(USER[0], vec![3, 4])
+        .failed(NFTPixelboardError::RoyaltiesMismatch);
+}
+
+#[test]
+fn batch_buying() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 100);
+    ft_program.mint(USER[1], 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        commission_percentage: 10,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    for token_id in 0..3 {
+        let x = token_id as BlockSideLength * 2;
+
+        pixelboard_program
+            .mint(USER[0], vec![0; 4], ((x, 0), (x + 2, 2)).into())
+            .succeed(token_id);
+    }
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(5))
+        .succeed(0);
+    pixelboard_program
+        .change_sale_state(USER[0], 1, Some(5))
+        .succeed(1);
+
+    // Should fail because the NFT isn't for sale.
+    pixelboard_program
+        .buy_batch(USER[1], vec![2], 100)
+        .failed(NFTPixelboardError::NFTIsNotOnSale);
+    // Should fail because the NFTs for sale cost 40 tokens in total.
+    pixelboard_program
+        .buy_batch(USER[1], vec![0, 1, 2], 39)
+        .failed(NFTPixelboardError::TotalPriceExceeded);
+
+    nft_program.fail_transfer(true);
+    // Should fail because none of the NFTs can be transferred, so the payment
+    // is refunded.
+    pixelboard_program
+        .buy_batch(USER[1], vec![0, 1], 40)
+        .failed(NFTPixelboardError::NFTTransferFailed);
+    nft_program.fail_transfer(false);
+    assert_eq!(ft_program.balance(USER[1]), 100);
+
+    nft_program.fail_transfer_of(1, true);
+    // A price of the NFT that can't be transferred is credited back to the
+    // buyer.
+    pixelboard_program
+        .buy_batch(USER[1], vec![0, 1], 40)
+        .succeed(vec![0]);
+    nft_program.fail_transfer_of(1, false);
+    assert_eq!(ft_program.balance(USER[1]), 60);
+    pixelboard_program.withdraw(USER[1], 20).succeed(20);
+
+    // NFTs that aren't for sale & duplicates are skipped.
+    pixelboard_program
+        .buy_batch(USER[1], vec![1, 2, 1], 20)
+        .succeed(vec![1]);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+    assert_eq!(nft_program.owner(1), Some(USER[1].into()));
+    assert_eq!(nft_program.owner(2), Some(USER[0].into()));
+    assert_eq!(ft_program.balance(USER[1]), 60);
+
+    // The owner gets minting fees & a 10% commission, and the seller gets the
+    // rest.
+    pixelboard_program.withdraw(OWNER, 16).succeed(16);
+    pixelboard_program.withdraw(USER[0], 36).succeed(36);
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 0);
 }
@@ -0,0 +1,201 @@
+pub mod utils;
+use utils::{prelude::*, MockFungibleToken, MockNonFungibleToken, OWNER, USER};
+
+#[test]
+fn pending_transactions() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], MAX_PIXEL_PRICE * 50);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program =
+        NFTPixelboard::initialize(&system, ft_program.actor_id(), nft_program.actor_id());
+
+    // Should fail because USER[0] doesn't have a pending transaction.
+    pixelboard_program
+        .continue_tx(USER[0])
+        .failed(NFTPixelboardError::TxNotFound);
+    // Should fail because USER[0] isn't the owner of the pixelboard.
+    pixelboard_program
+        .cancel_tx(USER[0], USER[0])
+        .failed(NFTPixelboardError::NotOwner);
+    // Should fail because USER[0] doesn't have a pending transaction.
+    pixelboard_program
+        .cancel_tx(OWNER, USER[0])
+        .failed(NFTPixelboardError::TxNotFound);
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((0, 0), (5, 5)).into())
+        .succeed(0);
+    pixelboard_program.paint(USER[0], 0, vec![1; 25]).succeed(0);
+
+    // Completed transactions don't block next ones.
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((5, 5), (10, 10)).into())
+        .succeed(1);
+    pixelboard_program
+        .continue_tx(USER[0])
+        .failed(NFTPixelboardError::TxNotFound);
+}
+
+#[test]
+fn interrupted_transactions() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program = NFTPixelboard::initialize_custom(
+        &system,
+        default_config(ft_program.actor_id(), nft_program.actor_id()),
+    )
+    .succeed();
+
+    // The NFT program doesn't reply, so the mint waits after the payment.
+    nft_program.hang(true);
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((0, 0), (5, 5)).into())
+        .interrupted();
+    assert_eq!(ft_program.balance(USER[0]), 0);
+
+    // Should fail because the mint may still get a reply.
+    pixelboard_program
+        .continue_tx(USER[0])
+        .failed(NFTPixelboardError::TxInProgress);
+    pixelboard_program
+        .cancel_tx(OWNER, USER[0])
+        .failed(NFTPixelboardError::TxInProgress);
+
+    system.spend_blocks(TX_TIMEOUT);
+    nft_program.hang(false);
+    pixelboard_program
+        .continue_tx(USER[0])
+        .succeed(NFTPixelboardEvent::Minted(0u128.into()));
+    assert_eq!(nft_program.owner(0), Some(USER[0].into()));
+    assert_eq!(ft_program.balance(pixelboard_program.actor_id()), 25);
+
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(2))
+        .succeed(0);
+
+    // The NFT program doesn't reply, so the purchase waits with its escrow.
+    nft_program.hang(true);
+    pixelboard_program.buy(USER[1], 0).interrupted();
+    assert_eq!(ft_program.balance(USER[1]), 100 - 50);
+
+    // Should fail because the purchase may still get a reply.
+    pixelboard_program
+        .cancel_tx(OWNER, USER[1])
+        .failed(NFTPixelboardError::TxInProgress);
+    pixelboard_program
+        .buy(USER[1], 0)
+        .failed(NFTPixelboardError::TxInProgress);
+
+    system.spend_blocks(TX_TIMEOUT);
+    nft_program.hang(false);
+    pixelboard_program
+        .cancel_tx(OWNER, USER[1])
+        .succeed(USER[1]);
+    assert_eq!(ft_program.balance(USER[1]), 100);
+    assert_eq!(nft_program.owner(0), Some(pixelboard_program.actor_id()));
+
+    // The NFT is still for sale after the purchase is cancelled.
+    pixelboard_program.buy(USER[1], 0).succeed(0);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+}
+
+#[test]
+fn unfinished_transactions() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program = NFTPixelboard::initialize_custom(
+        &system,
+        default_config(ft_program.actor_id(), nft_program.actor_id()),
+    )
+    .succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((0, 0), (5, 5)).into())
+        .succeed(0);
+
+    nft_program.fail_burn(true);
+    pixelboard_program
+        .burn(USER[0], 0, false)
+        .failed(NFTPixelboardError::NFTBurnFailed);
+    nft_program.fail_burn(false);
+
+    // Should fail because the NFT is already taken from USER[0].
+    pixelboard_program
+        .cancel_tx(OWNER, USER[0])
+        .failed(NFTPixelboardError::TxNotCancellable);
+    assert_eq!(nft_program.owner(0), Some(pixelboard_program.actor_id()));
+
+    pixelboard_program
+        .continue_tx(USER[0])
+        .succeed(NFTPixelboardEvent::Burned(0u128.into()));
+    pixelboard_program
+        .cancel_tx(OWNER, USER[0])
+        .failed(NFTPixelboardError::TxNotFound);
+}
+
+#[test]
+fn repriced_purchase_continuation() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 25);
+    ft_program.mint(USER[1], 100);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program = NFTPixelboard::initialize_custom(
+        &system,
+        default_config(ft_program.actor_id(), nft_program.actor_id()),
+    )
+    .succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((0, 0), (5, 5)).into())
+        .succeed(0);
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(2))
+        .succeed(0);
+
+    nft_program.hang(true);
+    pixelboard_program.buy(USER[1], 0).interrupted();
+    system.spend_blocks(TX_TIMEOUT);
+    nft_program.hang(false);
+
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(3))
+        .succeed(0);
+    // Should fail because the NFT got more expensive than the escrowed price.
+    pixelboard_program
+        .continue_tx(USER[1])
+        .failed(NFTPixelboardError::NFTIsNotOnSale);
+    assert_eq!(ft_program.balance(USER[1]), 100);
+    assert_eq!(nft_program.owner(0), Some(pixelboard_program.actor_id()));
+
+    nft_program.hang(true);
+    pixelboard_program.buy(USER[1], 0).interrupted();
+    system.spend_blocks(TX_TIMEOUT);
+    nft_program.hang(false);
+
+    // The NFT is bought at the current price & the rest of the escrow is
+    // credited back.
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(1))
+        .succeed(0);
+    pixelboard_program
+        .continue_tx(USER[1])
+        .succeed(NFTPixelboardEvent::Bought(0u128.into()));
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+    pixelboard_program.withdraw(USER[1], 50).succeed(50);
+    pixelboard_program.withdraw(USER[0], 25).succeed(25);
+}
@@ -0,0 +1,349 @@
+pub mod utils;
+use utils::{prelude::*, MockFungibleToken, MockNonFungibleToken, OWNER, USER};
+
+#[test]
+fn transferring() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], MAX_PIXEL_PRICE * 25);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program =
+        NFTPixelboard::initialize(&system, ft_program.actor_id(), nft_program.actor_id());
+    nft_program.check_approvals(pixelboard_program.actor_id());
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+
+    // Should fail because USER[1] isn't the owner of the NFT.
+    pixelboard_program
+        .transfer(USER[1], 0, USER[1])
+        .failed(NFTPixelboardError::NotOwner);
+    // Should fail because `to` address mustn't be `ActorId::zero()`.
+    pixelboard_program
+        .transfer(USER[0], 0, 0)
+        .failed(NFTPixelboardError::ZeroAddress);
+    // Should fail because the pixelboard program isn't approved to transfer the NFT.
+    pixelboard_program
+        .transfer(USER[0], 0, USER[1])
+        .failed(NFTPixelboardError::NFTTransferFailed);
+
+    nft_program.approve(0);
+    pixelboard_program.transfer(USER[0], 0, USER[1]).succeed(0);
+    assert_eq!(nft_program.owner(0), Some(USER[1].into()));
+
+    nft_program.approve(0);
+    pixelboard_program
+        .change_sale_state(USER[1], 0, Some(1))
+        .succeed(0);
+    // Should fail because the NFT is for sale.
+    pixelboard_program
+        .transfer(USER[1], 0, OWNER)
+        .failed(NFTPixelboardError::NFTIsOnSale);
+
+    // Painting rights follow the new owner.
+    pixelboard_program
+        .paint(USER[0], 0, vec![1; 25])
+        .failed(NFTPixelboardError::NotOwner);
+    pixelboard_program.paint(USER[1], 0, vec![1; 25]).succeed(0);
+}
+
+#[test]
+fn syncing_owners() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], MAX_PIXEL_PRICE * 50);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program =
+        NFTPixelboard::initialize(&system, ft_program.actor_id(), nft_program.actor_id());
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((0, 0), (5, 5)).into())
+        .succeed(0);
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((5, 5), (10, 10)).into())
+        .succeed(1);
+
+    // Should fail because the NFT doesn't exist.
+    pixelboard_program
+        .sync_owner(USER[1], 2)
+        .failed(NFTPixelboardError::NFTNotFoundById);
+
+    nft_program.transfer(USER[1], 0);
+    pixelboard_program.sync_owner(OWNER, 0).succeed(0);
+    pixelboard_program
+        .change_sale_state(USER[0], 0, Some(1))
+        .failed(NFTPixelboardError::NotOwner);
+
+    nft_program.transfer(USER[1], 1);
+    // The first NFT was already synced, so only the second one should be changed.
+    pixelboard_program
+        .sync_owners(OWNER, vec![0, 1])
+        .succeed(vec![1]);
+    pixelboard_program
+        .change_sale_state(USER[1], 1, Some(1))
+        .succeed(1);
+}
+
+#[test]
+fn merging() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], MAX_PIXEL_PRICE * 30);
+    ft_program.mint(USER[1], MAX_PIXEL_PRICE * 10);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program =
+        NFTPixelboard::initialize(&system, ft_program.actor_id(), nft_program.actor_id());
+    nft_program.check_approvals(pixelboard_program.actor_id());
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 10], ((0, 0), (5, 2)).into())
+        .succeed(0);
+    pixelboard_program
+        .mint(USER[0], vec![0; 10], ((5, 0), (10, 2)).into())
+        .succeed(1);
+    pixelboard_program
+        .mint(USER[0], vec![0; 10], ((0, 3), (5, 5)).into())
+        .succeed(2);
+    pixelboard_program
+        .mint(USER[1], vec![0; 10], ((5, 3), (10, 5)).into())
+        .succeed(3);
+
+    // Should fail because at least 2 NFTs must be merged.
+    pixelboard_program
+        .merge(USER[0], vec![0, 0])
+        .failed(NFTPixelboardError::NotEnoughNFTs);
+    // Should fail because USER[0] isn't the owner of the 4th NFT.
+    pixelboard_program
+        .merge(USER[0], vec![2, 3])
+        .failed(NFTPixelboardError::NotOwner);
+    // Should fail because there's a gap between rectangles of the NFTs.
+    pixelboard_program
+        .merge(USER[0], vec![0, 2])
+        .failed(NFTPixelboardError::RectanglesNotFormRectangle);
+
+    nft_program.approve(0);
+    nft_program.approve(1);
+    pixelboard_program.merge(USER[0], vec![0, 1]).succeed(4);
+    assert_eq!(nft_program.owner(4), Some(USER[0].into()));
+
+    // The old NFTs no longer exist on the pixelboard.
+    pixelboard_program
+        .paint(USER[0], 0, vec![0; 10])
+        .failed(NFTPixelboardError::NFTNotFoundById);
+}
+
+#[test]
+fn splitting() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 36);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        block_side_length: 2,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+    nft_program.check_approvals(pixelboard_program.actor_id());
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 36], ((2, 2), (8, 8)).into())
+        .succeed(0);
+
+    // Should fail because the line doesn't observe a block layout.
+    pixelboard_program
+        .split(USER[0], 0, SplitLine::Vertical(5))
+        .failed(NFTPixelboardError::WrongSplitLine);
+    // Should fail because the line lies on the NFT edge.
+    pixelboard_program
+        .split(USER[0], 0, SplitLine::Horizontal(8))
+        .failed(NFTPixelboardError::WrongSplitLine);
+    // Should fail because the line lies outside of the NFT.
+    pixelboard_program
+        .split(USER[0], 0, SplitLine::Horizontal(0))
+        .failed(NFTPixelboardError::WrongSplitLine);
+
+    nft_program.approve(0);
+    pixelboard_program
+        .split(USER[0], 0, SplitLine::Vertical(4))
+        .succeed((1, 2));
+    assert_eq!(nft_program.owner(1), Some(USER[0].into()));
+    assert_eq!(nft_program.owner(2), Some(USER[0].into()));
+
+    // The right part can be sold separately from the left one.
+    nft_program.approve(2);
+    pixelboard_program
+        .change_sale_state(USER[0], 2, Some(1))
+        .succeed(2);
+    pixelboard_program.paint(USER[0], 1, vec![1; 12]).succeed(1);
+}
+
+#[test]
+fn burning() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], MAX_PIXEL_PRICE * 50);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program =
+        NFTPixelboard::initialize(&system, ft_program.actor_id(), nft_program.actor_id());
+    nft_program.check_approvals(pixelboard_program.actor_id());
+
+    pixelboard_program
+        .mint(USER[0], vec![1; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+
+    // Should fail because USER[1] isn't the owner of the NFT.
+    pixelboard_program
+        .burn(USER[1], 0, true)
+        .failed(NFTPixelboardError::NotOwner);
+    // Should fail because the pixelboard program isn't approved to transfer the NFT.
+    pixelboard_program
+        .burn(USER[0], 0, true)
+        .failed(NFTPixelboardError::NFTTransferFailed);
+
+    nft_program.approve(0);
+    pixelboard_program.burn(USER[0], 0, true).succeed(0);
+
+    // Should fail because the NFT doesn't exist anymore.
+    pixelboard_program
+        .burn(USER[0], 0, true)
+        .failed(NFTPixelboardError::NFTNotFoundById);
+
+    // The freed area can be minted again.
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(1);
+}
+
+#[test]
+fn splitting_continuation() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 36);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+
+    let pixelboard_config = InitNFTPixelboard {
+        block_side_length: 2,
+        ..default_config(ft_program.actor_id(), nft_program.actor_id())
+    };
+    let pixelboard_program = NFTPixelboard::initialize_custom(&system, pixelboard_config).succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 36], ((2, 2), (8, 8)).into())
+        .succeed(0);
+
+    nft_program.fail_mint(true);
+    // Should fail because the NFT program fails to mint new NFTs.
+    pixelboard_program
+        .split(USER[0], 0, SplitLine::Vertical(4))
+        .failed(NFTPixelboardError::NFTMintFailed);
+    nft_program.fail_mint(false);
+
+    // The split NFT is already burned, so the split stays pending.
+    assert_eq!(nft_program.owner(0), None);
+    pixelboard_program
+        .paint(USER[0], 0, vec![1; 36])
+        .failed(NFTPixelboardError::PreviousTxMustBeCompleted);
+
+    pixelboard_program
+        .continue_tx(USER[0])
+        .succeed(NFTPixelboardEvent::Split(1u128.into(), 2u128.into()));
+    assert_eq!(nft_program.owner(1), Some(USER[0].into()));
+    assert_eq!(nft_program.owner(2), Some(USER[0].into()));
+    pixelboard_program.paint(USER[0], 1, vec![1; 12]).succeed(1);
+}
+
+#[test]
+fn merging_continuation() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 20);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program = NFTPixelboard::initialize_custom(
+        &system,
+        default_config(ft_program.actor_id(), nft_program.actor_id()),
+    )
+    .succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![0; 10], ((0, 0), (5, 2)).into())
+        .succeed(0);
+    pixelboard_program
+        .mint(USER[0], vec![0; 10], ((5, 0), (10, 2)).into())
+        .succeed(1);
+
+    nft_program.fail_mint(true);
+    // Should fail because the NFT program fails to mint the merged NFT.
+    pixelboard_program
+        .merge(USER[0], vec![0, 1])
+        .failed(NFTPixelboardError::NFTMintFailed);
+    nft_program.fail_mint(false);
+
+    // Merged NFTs are already burned, so the merge stays pending.
+    assert_eq!(nft_program.owner(0), None);
+    assert_eq!(nft_program.owner(1), None);
+    pixelboard_program
+        .paint(USER[0], 0, vec![1; 10])
+        .failed(NFTPixelboardError::PreviousTxMustBeCompleted);
+
+    pixelboard_program
+        .continue_tx(USER[0])
+        .succeed(NFTPixelboardEvent::Merged(2u128.into()));
+    assert_eq!(nft_program.owner(2), Some(USER[0].into()));
+    pixelboard_program.paint(USER[0], 2, vec![1; 20]).succeed(2);
+}
+
+#[test]
+fn burning_continuation() {
+    let system = utils::initialize_system();
+
+    let ft_program = MockFungibleToken::initialize(&system);
+    ft_program.mint(USER[0], 50);
+
+    let nft_program = MockNonFungibleToken::initialize(&system);
+    let pixelboard_program = NFTPixelboard::initialize_custom(
+        &system,
+        default_config(ft_program.actor_id(), nft_program.actor_id()),
+    )
+    .succeed();
+
+    pixelboard_program
+        .mint(USER[0], vec![1; 25], ((3, 3), (8, 8)).into())
+        .succeed(0);
+
+    nft_program.fail_burn(true);
+    // Should fail because the NFT program fails to burn the NFT.
+    pixelboard_program
+        .burn(USER[0], 0, true)
+        .failed(NFTPixelboardError::NFTBurnFailed);
+    nft_program.fail_burn(false);
+
+    // The NFT is already taken by the pixelboard, so the burn stays pending.
+    assert_eq!(nft_program.owner(0), Some(pixelboard_program.actor_id()));
+    pixelboard_program
+        .paint(USER[0], 0, vec![0; 25])
+        .failed(NFTPixelboardError::PreviousTxMustBeCompleted);
+
+    pixelboard_program
+        .continue_tx(USER[0])
+        .succeed(NFTPixelboardEvent::Burned(0u128.into()));
+    assert_eq!(nft_program.owner(0), None);
+    pixelboard_program
+        .mint(USER[0], vec![0; 25], ((3, 3), (8, 8)).into())
+        .succeed(1);
+}
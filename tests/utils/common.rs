@@ -70,6 +70,12 @@ impl<T, R: Encode, E: Encode> RunResult<T, R, E> {
         self.assert_contains(Err::<R, E>(error));
     }
 
+    // A message gets no reply while it's waiting for one from another program.
+    #[track_caller]
+    pub fn interrupted(self) {
+        assert!(self.result.log().is_empty());
+    }
+
     #[track_caller]
     fn common_succeed<V: Encode>(self, value: T, wrap: fn(R) -> V) {
         let event = (self.event)(value);
@@ -0,0 +1,327 @@
+use super::{Program, FOREIGN_USER};
+use ft_main_io::{FTokenAction, FTokenEvent, LogicAction};
+use gear_lib::non_fungible_token::token::TokenId;
+use gstd::{prelude::*, ActorId};
+use gtest::{Program as InnerProgram, System, WasmProgram};
+use nft_io::{NFTAction, NFTEvent, NFTTransfer};
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Debug, Default)]
+pub struct MockFTokenState {
+    pub balances: BTreeMap<ActorId, u128>,
+    /// Transfers to these accounts fail as if an FT program misbehaves.
+    pub failing_recipients: BTreeSet<ActorId>,
+    /// Replies to successful messages by their transaction ids.
+    pub replies: BTreeMap<u64, Vec<u8>>,
+}
+
+#[derive(Debug)]
+struct MockFToken(Rc<RefCell<MockFTokenState>>);
+
+impl WasmProgram for MockFToken {
+    fn init(&mut self, _: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        Ok(None)
+    }
+
+    fn handle(&mut self, payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        let action = FTokenAction::decode(&mut &payload[..]).map_err(|_| "Wrong payload")?;
+        let mut state = self.0.borrow_mut();
+
+        // An FT program replies to a repeated transaction without repeating it.
+        if let FTokenAction::Message { transaction_id, .. } = action {
+            if let Some(reply) = state.replies.get(&transaction_id) {
+                return Ok(Some(reply.clone()));
+            }
+        }
+
+        let event = match action {
+            FTokenAction::Message {
+                transaction_id,
+                payload:
+                    LogicAction::Transfer {
+                        sender,
+                        recipient,
+                        amount,
+                    },
+                ..
+            } => {
+                let sender_balance = state.balances.get(&sender).copied().unwrap_or_default();
+
+                if sender_balance < amount || state.failing_recipients.contains(&recipient) {
+                    FTokenEvent::Err
+                } else {
+                    state.balances.insert(sender, sender_balance - amount);
+                    *state.balances.entry(recipient).or_default() += amount;
+                    state
+                        .replies
+                        .insert(transaction_id, FTokenEvent::Ok.encode());
+
+                    FTokenEvent::Ok
+                }
+            }
+            FTokenAction::GetBalance(account) => {
+                FTokenEvent::Balance(state.balances.get(&account).copied().unwrap_or_default())
+            }
+            _ => return Err("Unsupported action"),
+        };
+
+        Ok(Some(event.encode()))
+    }
+
+    fn handle_reply(&mut self, _: Vec<u8>) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn handle_signal(&mut self, _: Vec<u8>) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn state(&mut self) -> Result<Vec<u8>, &'static str> {
+        Ok(vec![])
+    }
+}
+
+/// An FT program mock with a shared state that can be changed from tests.
+pub struct MockFungibleToken<'a>(InnerProgram<'a>, Rc<RefCell<MockFTokenState>>);
+
+impl Program for MockFungibleToken<'_> {
+    fn inner_program(&self) -> &InnerProgram {
+        &self.0
+    }
+}
+
+impl<'a> MockFungibleToken<'a> {
+    pub fn initialize(system: &'a System) -> Self {
+        let state = Rc::new(RefCell::new(MockFTokenState::default()));
+        let program = InnerProgram::mock(system, MockFToken(state.clone()));
+
+        assert!(!program.send(FOREIGN_USER, 0).main_failed());
+
+        Self(program, state)
+    }
+
+    pub fn mint(&self, recipient: impl Into<ActorId>, amount: u128) {
+        *self
+            .1
+            .borrow_mut()
+            .balances
+            .entry(recipient.into())
+            .or_default() += amount;
+    }
+
+    pub fn balance(&self, account: impl Into<ActorId>) -> u128 {
+        self.1
+            .borrow()
+            .balances
+            .get(&account.into())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn fail_transfers_to(&self, recipient: impl Into<ActorId>, fail: bool) {
+        let mut state = self.1.borrow_mut();
+
+        if fail {
+            state.failing_recipients.insert(recipient.into());
+        } else {
+            state.failing_recipients.remove(&recipient.into());
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MockNFTokenState {
+    pub owners: BTreeMap<TokenId, ActorId>,
+    pub minted: u64,
+    /// A pixelboard that must be approved to transfer NFTs held by other
+    /// accounts. Approvals aren't checked if it's [`None`].
+    pub operator: Option<ActorId>,
+    /// NFTs that `operator` is approved to transfer.
+    pub approvals: BTreeSet<TokenId>,
+    pub fail_mint: bool,
+    pub fail_transfer: bool,
+    pub fail_burn: bool,
+    /// Transfers of these NFTs fail as if an NFT program misbehaves.
+    pub failing_tokens: BTreeSet<TokenId>,
+    /// Messages get no reply, so a pixelboard waits for one as if its
+    /// transaction was interrupted.
+    pub hang: bool,
+    /// Replies to successful messages by their transaction ids.
+    pub replies: BTreeMap<u64, Vec<u8>>,
+}
+
+#[derive(Debug)]
+struct MockNFToken(Rc<RefCell<MockNFTokenState>>);
+
+impl WasmProgram for MockNFToken {
+    fn init(&mut self, _: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        Ok(None)
+    }
+
+    // An NFT program panics on failures, so the mock does the same.
+    fn handle(&mut self, payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        let action = NFTAction::decode(&mut &payload[..]).map_err(|_| "Wrong payload")?;
+        let mut state = self.0.borrow_mut();
+
+        if state.hang {
+            return Ok(None);
+        }
+
+        // An NFT program replies to a repeated transaction without repeating
+        // it.
+        let transaction_id = match action {
+            NFTAction::Mint { transaction_id, .. }
+            | NFTAction::Transfer { transaction_id, .. }
+            | NFTAction::Burn { transaction_id, .. } => Some(transaction_id),
+            _ => None,
+        };
+        if let Some(reply) = transaction_id.and_then(|id| state.replies.get(&id)) {
+            return Ok(Some(reply.clone()));
+        }
+
+        let event = match action {
+            NFTAction::Mint { .. } => {
+                if state.fail_mint {
+                    return Err("Mint failed");
+                }
+
+                let token_id: TokenId = state.minted.into();
+                state.minted += 1;
+                // A minter isn't known to a mock, so a zero address stands for it.
+                state.owners.insert(token_id, ActorId::zero());
+
+                NFTEvent::Transfer(NFTTransfer {
+                    from: ActorId::zero(),
+                    to: ActorId::zero(),
+                    token_id,
+                })
+            }
+            NFTAction::Transfer { to, token_id, .. } => {
+                if state.fail_transfer || state.failing_tokens.contains(&token_id) {
+                    return Err("Transfer failed");
+                }
+
+                let from = *state.owners.get(&token_id).ok_or("Token not found")?;
+                // NFTs held by a minter or `operator` itself don't need approvals.
+                if let Some(operator) = state.operator {
+                    if from != operator
+                        && from != ActorId::zero()
+                        && !state.approvals.contains(&token_id)
+                    {
+                        return Err("Transfer isn't approved");
+                    }
+                }
+                state.approvals.remove(&token_id);
+                state.owners.insert(token_id, to);
+
+                NFTEvent::Transfer(NFTTransfer { from, to, token_id })
+            }
+            NFTAction::Burn { token_id, .. } => {
+                if state.fail_burn {
+                    return Err("Burn failed");
+                }
+
+                let from = state.owners.remove(&token_id).ok_or("Token not found")?;
+
+                NFTEvent::Transfer(NFTTransfer {
+                    from,
+                    to: ActorId::zero(),
+                    token_id,
+                })
+            }
+            NFTAction::Owner { token_id } => NFTEvent::Owner {
+                owner: *state.owners.get(&token_id).ok_or("Token not found")?,
+                token_id,
+            },
+            _ => return Err("Unsupported action"),
+        };
+
+        let reply = event.encode();
+        if let Some(transaction_id) = transaction_id {
+            state.replies.insert(transaction_id, reply.clone());
+        }
+
+        Ok(Some(reply))
+    }
+
+    fn handle_reply(&mut self, _: Vec<u8>) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn handle_signal(&mut self, _: Vec<u8>) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn state(&mut self) -> Result<Vec<u8>, &'static str> {
+        Ok(vec![])
+    }
+}
+
+/// An NFT program mock with a shared state that can be changed from tests.
+pub struct MockNonFungibleToken<'a>(InnerProgram<'a>, Rc<RefCell<MockNFTokenState>>);
+
+impl Program for MockNonFungibleToken<'_> {
+    fn inner_program(&self) -> &InnerProgram {
+        &self.0
+    }
+}
+
+impl<'a> MockNonFungibleToken<'a> {
+    pub fn initialize(system: &'a System) -> Self {
+        let state = Rc::new(RefCell::new(MockNFTokenState::default()));
+        let program = InnerProgram::mock(system, MockNFToken(state.clone()));
+
+        assert!(!program.send(FOREIGN_USER, 0).main_failed());
+
+        Self(program, state)
+    }
+
+    pub fn owner(&self, token_id: u128) -> Option<ActorId> {
+        self.1.borrow().owners.get(&token_id.into()).copied()
+    }
+
+    /// Makes transfers of NFTs held by other accounts fail unless they're
+    /// approved to `operator`, as an NFT program does.
+    pub fn check_approvals(&self, operator: impl Into<ActorId>) {
+        self.1.borrow_mut().operator = Some(operator.into());
+    }
+
+    /// Approves an NFT to `operator` as its owner would do.
+    pub fn approve(&self, token_id: u128) {
+        self.1.borrow_mut().approvals.insert(token_id.into());
+    }
+
+    /// Transfers an NFT bypassing a pixelboard, as its owner would do.
+    pub fn transfer(&self, to: impl Into<ActorId>, token_id: u128) {
+        let mut state = self.1.borrow_mut();
+
+        state.approvals.remove(&token_id.into());
+        state.owners.insert(token_id.into(), to.into());
+    }
+
+    pub fn fail_mint(&self, fail: bool) {
+        self.1.borrow_mut().fail_mint = fail;
+    }
+
+    pub fn fail_transfer(&self, fail: bool) {
+        self.1.borrow_mut().fail_transfer = fail;
+    }
+
+    pub fn fail_burn(&self, fail: bool) {
+        self.1.borrow_mut().fail_burn = fail;
+    }
+
+    pub fn hang(&self, hang: bool) {
+        self.1.borrow_mut().hang = hang;
+    }
+
+    pub fn fail_transfer_of(&self, token_id: u128, fail: bool) {
+        let mut state = self.1.borrow_mut();
+
+        if fail {
+            state.failing_tokens.insert(token_id.into());
+        } else {
+            state.failing_tokens.remove(&token_id.into());
+        }
+    }
+}
@@ -10,8 +10,13 @@ pub use nftoken::*;
 mod common;
 pub use common::*;
 
+mod mocks;
+pub use mocks::*;
+
 pub mod prelude;
 
+pub const STATE_WASM: &str = "target/wasm32-unknown-unknown/debug/nft_pixelboard_state.meta.wasm";
+
 pub const FOREIGN_USER: u64 = 12345;
 pub const OWNER: u64 = 54321;
 pub const USER: [u64; 2] = [3746287346, 13856289765];
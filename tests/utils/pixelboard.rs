@@ -1,5 +1,5 @@
-use super::common::{InitResult, Program, RunResult};
-use super::{FOREIGN_USER, OWNER};
+use super::common::{InitResult, MetaStateReply, Program, RunResult};
+use super::{FOREIGN_USER, OWNER, STATE_WASM};
 use gear_lib::non_fungible_token::token::TokenMetadata;
 use gstd::ActorId;
 use gtest::{Program as InnerProgram, System, EXISTENTIAL_DEPOSIT};
@@ -15,19 +15,32 @@ impl Program for NFTPixelboard<'_> {
     }
 }
 
+/// Returns a config of a 10×10 pixelboard with 1×1 blocks, a pixel price of 1
+/// & no commission. Tests override only fields they depend on.
+pub fn default_config(ft_program: ActorId, nft_program: ActorId) -> InitNFTPixelboard {
+    InitNFTPixelboard {
+        ft_program,
+        block_side_length: 1,
+        nft_program,
+        owner: OWNER.into(),
+        painting: vec![0; 100],
+        pixel_price: 1,
+        commission_percentage: 0,
+        royalty_percentage: 0,
+        pricing_curve: None,
+        resolution: (10, 10).into(),
+        payment_mode: PaymentMode::FungibleToken,
+    }
+}
+
 impl<'a> NFTPixelboard<'a> {
     pub fn initialize(system: &'a System, ft_program: ActorId, nft_program: ActorId) -> Self {
         Self::initialize_custom(
             system,
             InitNFTPixelboard {
-                ft_program,
-                block_side_length: 1,
-                nft_program,
-                owner: OWNER.into(),
-                painting: vec![0; 100],
                 pixel_price: MAX_PIXEL_PRICE,
                 commission_percentage: 100,
-                resolution: (10, 10).into(),
+                ..default_config(ft_program, nft_program)
             },
         )
         .succeed()
@@ -69,12 +82,102 @@ impl<'a> NFTPixelboard<'a> {
                     painting,
                     rectangle,
                     token_metadata,
+                    currency: None,
+                    royalty_percentage: None,
+                },
+            ),
+            |value| NFTPixelboardEvent::Minted(value.into()),
+        )
+    }
+
+    pub fn mint_with_royalty(
+        &self,
+        from: u64,
+        painting: Vec<Color>,
+        rectangle: Rectangle,
+        royalty_percentage: u8,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Mint {
+                    painting,
+                    rectangle,
+                    token_metadata: Default::default(),
+                    currency: None,
+                    royalty_percentage: Some(royalty_percentage),
                 },
             ),
             |value| NFTPixelboardEvent::Minted(value.into()),
         )
     }
 
+    pub fn mint_in_currency(
+        &self,
+        from: u64,
+        painting: Vec<Color>,
+        rectangle: Rectangle,
+        currency: ActorId,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Mint {
+                    painting,
+                    rectangle,
+                    token_metadata: Default::default(),
+                    currency: Some(currency),
+                    royalty_percentage: None,
+                },
+            ),
+            |value| NFTPixelboardEvent::Minted(value.into()),
+        )
+    }
+
+    pub fn mint_batch(
+        &self,
+        from: u64,
+        tokens: Vec<(Rectangle, Vec<Color>)>,
+    ) -> NFTPixelboardRunResult<Vec<u128>> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::MintBatch(
+                    tokens
+                        .into_iter()
+                        .map(|(rectangle, painting)| (rectangle, Default::default(), painting))
+                        .collect(),
+                ),
+            ),
+            |token_ids| {
+                NFTPixelboardEvent::MintedBatch(token_ids.into_iter().map(Into::into).collect())
+            },
+        )
+    }
+
+    pub fn mint_with_value(
+        &self,
+        from: u64,
+        painting: Vec<Color>,
+        rectangle: Rectangle,
+        value: u128,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send_with_value(
+                from,
+                NFTPixelboardAction::Mint {
+                    painting,
+                    rectangle,
+                    token_metadata: Default::default(),
+                    currency: None,
+                    royalty_percentage: None,
+                },
+                value,
+            ),
+            |value| NFTPixelboardEvent::Minted(value.into()),
+        )
+    }
+
     pub fn change_sale_state(
         &self,
         from: u64,
@@ -87,6 +190,27 @@ impl<'a> NFTPixelboard<'a> {
                 NFTPixelboardAction::ChangeSaleState {
                     token_id: token_id.into(),
                     pixel_price,
+                    currency: None,
+                },
+            ),
+            |token_id| NFTPixelboardEvent::SaleStateChanged(token_id.into()),
+        )
+    }
+
+    pub fn change_sale_state_in_currency(
+        &self,
+        from: u64,
+        token_id: u128,
+        pixel_price: u128,
+        currency: ActorId,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::ChangeSaleState {
+                    token_id: token_id.into(),
+                    pixel_price: Some(pixel_price),
+                    currency: Some(currency),
                 },
             ),
             |token_id| NFTPixelboardEvent::SaleStateChanged(token_id.into()),
@@ -95,7 +219,71 @@ impl<'a> NFTPixelboard<'a> {
 
     pub fn buy(&self, from: u64, token_id: u128) -> NFTPixelboardRunResult<u128> {
         RunResult::new(
-            self.0.send(from, NFTPixelboardAction::Buy(token_id.into())),
+            self.0.send(
+                from,
+                NFTPixelboardAction::Buy {
+                    token_id: token_id.into(),
+                    currency: None,
+                },
+            ),
+            |token_id| NFTPixelboardEvent::Bought(token_id.into()),
+        )
+    }
+
+    pub fn buy_batch(
+        &self,
+        from: u64,
+        token_ids: Vec<u128>,
+        max_total_price: u128,
+    ) -> NFTPixelboardRunResult<Vec<u128>> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::BuyBatch {
+                    token_ids: token_ids.into_iter().map(Into::into).collect(),
+                    max_total_price,
+                    currency: None,
+                },
+            ),
+            |token_ids| {
+                NFTPixelboardEvent::BoughtBatch(token_ids.into_iter().map(Into::into).collect())
+            },
+        )
+    }
+
+    pub fn buy_in_currency(
+        &self,
+        from: u64,
+        token_id: u128,
+        currency: ActorId,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Buy {
+                    token_id: token_id.into(),
+                    currency: Some(currency),
+                },
+            ),
+            |token_id| NFTPixelboardEvent::Bought(token_id.into()),
+        )
+    }
+
+    pub fn buy_with_value(
+        &self,
+        from: u64,
+        token_id: u128,
+        value: u128,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send_with_value(
+                from,
+                NFTPixelboardAction::Buy {
+                    token_id: token_id.into(),
+                    currency: None,
+                },
+                value,
+            ),
             |token_id| NFTPixelboardEvent::Bought(token_id.into()),
         )
     }
@@ -117,6 +305,301 @@ impl<'a> NFTPixelboard<'a> {
             |token_id| NFTPixelboardEvent::Painted(token_id.into()),
         )
     }
+
+    pub fn start_auction(
+        &self,
+        from: u64,
+        token_id: u128,
+        min_pixel_price: u128,
+        duration: BlockHeight,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::StartAuction {
+                    token_id: token_id.into(),
+                    min_pixel_price,
+                    duration,
+                },
+            ),
+            |token_id| NFTPixelboardEvent::AuctionStarted(token_id.into()),
+        )
+    }
+
+    pub fn bid(
+        &self,
+        from: u64,
+        token_id: u128,
+        pixel_price: u128,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Bid {
+                    token_id: token_id.into(),
+                    pixel_price,
+                },
+            ),
+            |token_id| NFTPixelboardEvent::BidPlaced(token_id.into()),
+        )
+    }
+
+    pub fn settle_auction(&self, from: u64, token_id: u128) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0
+                .send(from, NFTPixelboardAction::SettleAuction(token_id.into())),
+            |token_id| NFTPixelboardEvent::AuctionSettled(token_id.into()),
+        )
+    }
+
+    pub fn start_dutch_auction(
+        &self,
+        from: u64,
+        token_id: u128,
+        start_pixel_price: u128,
+        floor_pixel_price: u128,
+        duration: BlockHeight,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::StartDutchAuction {
+                    token_id: token_id.into(),
+                    start_pixel_price,
+                    floor_pixel_price,
+                    duration,
+                },
+            ),
+            |token_id| NFTPixelboardEvent::DutchAuctionStarted(token_id.into()),
+        )
+    }
+
+    pub fn make_offer(
+        &self,
+        from: u64,
+        token_id: u128,
+        pixel_price: u128,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::MakeOffer {
+                    token_id: token_id.into(),
+                    pixel_price,
+                },
+            ),
+            |token_id| NFTPixelboardEvent::OfferMade(token_id.into()),
+        )
+    }
+
+    pub fn cancel_offer(&self, from: u64, token_id: u128) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0
+                .send(from, NFTPixelboardAction::CancelOffer(token_id.into())),
+            |token_id| NFTPixelboardEvent::OfferCancelled(token_id.into()),
+        )
+    }
+
+    pub fn accept_offer(
+        &self,
+        from: u64,
+        token_id: u128,
+        offerer: u64,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::AcceptOffer {
+                    token_id: token_id.into(),
+                    offerer: offerer.into(),
+                },
+            ),
+            |token_id| NFTPixelboardEvent::OfferAccepted(token_id.into()),
+        )
+    }
+
+    pub fn transfer(&self, from: u64, token_id: u128, to: u64) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Transfer {
+                    token_id: token_id.into(),
+                    to: to.into(),
+                },
+            ),
+            |token_id| NFTPixelboardEvent::Transferred(token_id.into()),
+        )
+    }
+
+    pub fn sync_owner(&self, from: u64, token_id: u128) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0
+                .send(from, NFTPixelboardAction::SyncOwner(token_id.into())),
+            |token_id| NFTPixelboardEvent::OwnerSynced(token_id.into()),
+        )
+    }
+
+    pub fn sync_owners(
+        &self,
+        from: u64,
+        token_ids: Vec<u128>,
+    ) -> NFTPixelboardRunResult<Vec<u128>> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::SyncOwners(token_ids.into_iter().map(Into::into).collect()),
+            ),
+            |token_ids| {
+                NFTPixelboardEvent::OwnersSynced(token_ids.into_iter().map(Into::into).collect())
+            },
+        )
+    }
+
+    pub fn merge(&self, from: u64, token_ids: Vec<u128>) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Merge {
+                    token_ids: token_ids.into_iter().map(Into::into).collect(),
+                    token_metadata: Default::default(),
+                },
+            ),
+            |token_id| NFTPixelboardEvent::Merged(token_id.into()),
+        )
+    }
+
+    pub fn split(
+        &self,
+        from: u64,
+        token_id: u128,
+        cut: SplitLine,
+    ) -> NFTPixelboardRunResult<(u128, u128)> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Split {
+                    token_id: token_id.into(),
+                    cut,
+                    token_metadata: Default::default(),
+                },
+            ),
+            |(first, second)| NFTPixelboardEvent::Split(first.into(), second.into()),
+        )
+    }
+
+    pub fn burn(
+        &self,
+        from: u64,
+        token_id: u128,
+        restore_background: bool,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Burn {
+                    token_id: token_id.into(),
+                    restore_background,
+                },
+            ),
+            |token_id| NFTPixelboardEvent::Burned(token_id.into()),
+        )
+    }
+
+    pub fn update_config(
+        &self,
+        from: u64,
+        update: ConfigUpdate,
+    ) -> NFTPixelboardRunResult<NFTPixelboardEvent> {
+        RunResult::new(
+            self.0.send(from, NFTPixelboardAction::UpdateConfig(update)),
+            |event| event,
+        )
+    }
+
+    pub fn propose_owner(&self, from: u64, owner: u64) -> NFTPixelboardRunResult<u64> {
+        RunResult::new(
+            self.0
+                .send(from, NFTPixelboardAction::ProposeOwner(owner.into())),
+            |owner| NFTPixelboardEvent::OwnerProposed(owner.into()),
+        )
+    }
+
+    pub fn accept_ownership(&self, from: u64) -> NFTPixelboardRunResult<u64> {
+        RunResult::new(
+            self.0.send(from, NFTPixelboardAction::AcceptOwnership),
+            |owner| NFTPixelboardEvent::OwnershipAccepted(owner.into()),
+        )
+    }
+
+    pub fn pause(&self, from: u64, kind: ActionKind) -> NFTPixelboardRunResult<ActionKind> {
+        RunResult::new(
+            self.0.send(from, NFTPixelboardAction::Pause(kind)),
+            NFTPixelboardEvent::Paused,
+        )
+    }
+
+    pub fn unpause(&self, from: u64, kind: ActionKind) -> NFTPixelboardRunResult<ActionKind> {
+        RunResult::new(
+            self.0.send(from, NFTPixelboardAction::Unpause(kind)),
+            NFTPixelboardEvent::Unpaused,
+        )
+    }
+
+    pub fn continue_tx(&self, from: u64) -> NFTPixelboardRunResult<NFTPixelboardEvent> {
+        RunResult::new(
+            self.0.send(from, NFTPixelboardAction::ContinueTx),
+            |event| event,
+        )
+    }
+
+    pub fn cancel_tx(&self, from: u64, user: u64) -> NFTPixelboardRunResult<u64> {
+        RunResult::new(
+            self.0
+                .send(from, NFTPixelboardAction::CancelTx(user.into())),
+            |user| NFTPixelboardEvent::TxCancelled(user.into()),
+        )
+    }
+
+    pub fn withdraw(&self, from: u64, amount: u128) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Withdraw {
+                    currency: None,
+                    amount,
+                },
+            ),
+            NFTPixelboardEvent::Withdrawn,
+        )
+    }
+
+    pub fn withdraw_in_currency(
+        &self,
+        from: u64,
+        currency: ActorId,
+        amount: u128,
+    ) -> NFTPixelboardRunResult<u128> {
+        RunResult::new(
+            self.0.send(
+                from,
+                NFTPixelboardAction::Withdraw {
+                    currency: Some(currency),
+                    amount,
+                },
+            ),
+            NFTPixelboardEvent::Withdrawn,
+        )
+    }
+
+    pub fn pixel_info(&self, coordinates: Coordinates) -> MetaStateReply<Option<Token>> {
+        let state_wasm = std::fs::read(STATE_WASM).expect("Unable to read the state WASM");
+
+        MetaStateReply(
+            self.0
+                .read_state_using_wasm("pixel_info", state_wasm, Some(coordinates))
+                .expect("Unable to read the state"),
+        )
+    }
 }
 
 pub struct NFTPixelboardInit<'a>(InnerProgram<'a>, bool);
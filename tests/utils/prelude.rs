@@ -1,3 +1,3 @@
-pub use super::{NFTPixelboard, Program};
+pub use super::{default_config, NFTPixelboard, Program};
 pub use gstd::prelude::*;
 pub use nft_pixelboard_io::*;